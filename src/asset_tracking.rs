@@ -1,8 +1,13 @@
 //! 一种将资源句柄集合作为资源加载的高级方法。
 
-use std::collections::VecDeque;
+use std::{collections::VecDeque, marker::PhantomData};
 
-use bevy::prelude::*;
+use bevy::{
+    asset::{AssetLoader, LoadContext, RecursiveDependencyLoadState, io::Reader},
+    prelude::*,
+};
+use serde::de::DeserializeOwned;
+use thiserror::Error;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<ResourceHandles>();
@@ -14,6 +19,19 @@ pub trait LoadResource {
     /// 都已加载时，它将作为资源插入。这确保了资源仅在
     /// 资源准备就绪时存在。
     fn load_resource<T: Resource + Asset + Clone + FromWorld>(&mut self) -> &mut Self;
+
+    /// 从 `path` 处的资源文件加载 [`Resource`]。与 [`LoadResource::load_resource`] 一样，
+    /// 它会在加载完成后才作为资源插入。文件被修改（例如 `dev_native` 下的热重载）时，
+    /// 资源会被重新插入。
+    ///
+    /// 文件加载失败时资源不会被插入，使用它的系统应以 `resource_exists` 为运行条件，
+    /// 或使用 `Option<Res<T>>`。修复文件后热重载成功时资源会被插入。
+    ///
+    /// 需要事先为 `T` 注册一个 [`AssetLoader`]，例如 [`RonAssetLoader`]。
+    fn load_resource_from_path<T: Resource + Asset + Clone>(
+        &mut self,
+        path: &'static str,
+    ) -> &mut Self;
 }
 
 impl LoadResource for App {
@@ -34,6 +52,26 @@ impl LoadResource for App {
             }));
         self
     }
+
+    fn load_resource_from_path<T: Resource + Asset + Clone>(
+        &mut self,
+        path: &'static str,
+    ) -> &mut Self {
+        self.init_asset::<T>();
+        let handle: Handle<T> = self.world().resource::<AssetServer>().load(path);
+        self.insert_resource(ResourceAssetHandle(handle.clone()));
+        self.add_systems(PreUpdate, reload_resource_asset::<T>);
+        let mut handles = self.world_mut().resource_mut::<ResourceHandles>();
+        handles
+            .waiting
+            .push_back((handle.untyped(), |world, handle| {
+                let assets = world.resource::<Assets<T>>();
+                if let Some(value) = assets.get(handle.id().typed::<T>()) {
+                    world.insert_resource(value.clone());
+                }
+            }));
+        self
+    }
 }
 
 /// 一个函数，用于插入已加载的资源。
@@ -45,6 +83,8 @@ pub struct ResourceHandles {
     // `finished`。
     waiting: VecDeque<(UntypedHandle, InsertLoadedResource)>,
     finished: Vec<UntypedHandle>,
+    /// 加载失败的资源。保留句柄，以便文件修复并重新加载后仍能插入资源。
+    failed: Vec<(UntypedHandle, InsertLoadedResource)>,
 }
 
 impl ResourceHandles {
@@ -62,10 +102,105 @@ fn load_resource_assets(world: &mut World) {
                 if assets.is_loaded_with_dependencies(&handle) {
                     insert_fn(world, &handle);
                     resource_handles.finished.push(handle);
+                } else if let RecursiveDependencyLoadState::Failed(error) =
+                    assets.recursive_dependency_load_state(&handle)
+                {
+                    // 不再等待加载失败的资源，否则加载界面永远不会结束。依赖这个资源的功能将不可用。
+                    match handle.path() {
+                        Some(path) => error!("Failed to load resource {path}: {error}"),
+                        None => error!("Failed to load resource: {error}"),
+                    }
+                    resource_handles.failed.push((handle, insert_fn));
                 } else {
                     resource_handles.waiting.push_back((handle, insert_fn));
                 }
             }
+            // 加载失败的资源重新加载成功后（例如修复文件后的热重载）再插入。
+            let failed = std::mem::take(&mut resource_handles.failed);
+            for (handle, insert_fn) in failed {
+                if assets.is_loaded_with_dependencies(&handle) {
+                    insert_fn(world, &handle);
+                    resource_handles.finished.push(handle);
+                } else {
+                    resource_handles.failed.push((handle, insert_fn));
+                }
+            }
         });
     });
 }
+
+/// 由 [`LoadResource::load_resource_from_path`] 加载的资源文件句柄。
+#[derive(Resource)]
+struct ResourceAssetHandle<T: Asset>(Handle<T>);
+
+/// 资源文件被修改后，重新插入对应的资源。之前加载失败的文件第一次加载成功时由
+/// [`load_resource_assets`] 插入。
+fn reload_resource_asset<T: Resource + Asset + Clone>(
+    mut commands: Commands,
+    mut asset_events: MessageReader<AssetEvent<T>>,
+    handle: Res<ResourceAssetHandle<T>>,
+    assets: Res<Assets<T>>,
+) {
+    for event in asset_events.read() {
+        if event.is_modified(&handle.0)
+            && let Some(value) = assets.get(&handle.0)
+        {
+            info!("Reloaded {}", std::any::type_name::<T>());
+            commands.insert_resource(value.clone());
+        }
+    }
+}
+
+/// 可以由 [`RonAssetLoader`] 从 RON 文件加载的 [`Asset`]。
+pub trait RonAsset: Asset + DeserializeOwned {
+    /// 此资源文件的扩展名（不含前导点），例如 `games.ron`。
+    const EXTENSIONS: &'static [&'static str];
+
+    /// 在反序列化之后校验数据。返回的错误会作为加载失败记录到日志中。
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// 一个通用的 [`AssetLoader`]，用于加载实现了 [`RonAsset`] 的数据文件。
+pub struct RonAssetLoader<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for RonAssetLoader<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// [`RonAssetLoader`] 可能遇到的错误。
+#[derive(Debug, Error)]
+pub enum RonAssetLoaderError {
+    #[error("could not read file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse RON: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("invalid data: {0}")]
+    Invalid(String),
+}
+
+impl<T: RonAsset> AssetLoader for RonAssetLoader<T> {
+    type Asset = T;
+    type Settings = ();
+    type Error = RonAssetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let asset: T = ron::de::from_bytes(&bytes)?;
+        asset.validate().map_err(RonAssetLoaderError::Invalid)?;
+        Ok(asset)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        T::EXTENSIONS
+    }
+}
@@ -0,0 +1,17 @@
+//! 音效的名称。
+//!
+//! 各音效的声音文件和播放参数在 `assets/sounds.ron` 中，见 [`sound_effect`](super::sound_effect)。
+
+/// 玩家的脚步声
+pub const STEP: &str = "step";
+/// 鼠标悬停在按钮上
+pub const BUTTON_HOVER: &str = "button_hover";
+/// 点击按钮
+pub const BUTTON_CLICK: &str = "button_click";
+/// 防御塔开火
+pub const TOWER_SHOOT: &str = "tower_shoot";
+/// 炮弹击中敌人
+pub const TOWER_HIT: &str = "tower_hit";
+
+/// 代码中使用的所有音效，音效文件中必须定义它们。
+pub const ALL: &[&str] = &[STEP, BUTTON_HOVER, BUTTON_CLICK, TOWER_SHOOT, TOWER_HIT];
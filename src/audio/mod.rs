@@ -0,0 +1,194 @@
+//! 音频的分类和音量。
+//!
+//! 每个声音属于一个类别（[`Music`]、[`SoundEffect`] 或 [`UiSound`]），实际音量为
+//! 主音量、类别音量和声音自身音量的乘积，见 [`VolumeBuses`]。
+//! 音乐按状态通过 [`AddStateMusic`] 声明，由 [`MusicDirector`](music::MusicDirector)
+//! 统一播放，见 [`music`]；音效通过 [`PlaySound`] 消息按名称播放，
+//! 见 [`sound_effect`]。
+
+pub mod cues;
+pub mod music;
+pub mod sound_effect;
+
+use bevy::{audio::Volume, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use self::music::MusicFade;
+pub use self::{music::AddStateMusic, sound_effect::PlaySound};
+use crate::i18n::config::{
+    SETTINGS_MASTER_VOLUME, SETTINGS_MUSIC_VOLUME, SETTINGS_SFX_VOLUME, SETTINGS_UI_VOLUME,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((music::plugin, sound_effect::plugin));
+
+    app.register_type::<Music>();
+    app.register_type::<SoundEffect>();
+    app.register_type::<UiSound>();
+    app.register_type::<VolumeBuses>();
+    app.init_resource::<VolumeBuses>();
+
+    app.add_observer(apply_volume_on_play);
+    app.add_systems(
+        Update,
+        update_volume.run_if(resource_changed::<VolumeBuses>.or(resource_changed::<GlobalVolume>)),
+    );
+}
+
+/// 一条音量总线。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub enum VolumeBus {
+    /// 主音量，作用于所有声音。
+    Master,
+    Music,
+    SoundEffect,
+    Ui,
+}
+
+impl VolumeBus {
+    /// 所有音量总线，按设置菜单中的顺序排列。
+    pub const ALL: [Self; 4] = [Self::Master, Self::Music, Self::SoundEffect, Self::Ui];
+
+    /// 音量名称的文本键。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Master => SETTINGS_MASTER_VOLUME,
+            Self::Music => SETTINGS_MUSIC_VOLUME,
+            Self::SoundEffect => SETTINGS_SFX_VOLUME,
+            Self::Ui => SETTINGS_UI_VOLUME,
+        }
+    }
+}
+
+/// 各音量总线的线性音量。
+#[derive(Resource, Reflect, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct VolumeBuses {
+    pub master: f32,
+    pub music: f32,
+    pub sound_effect: f32,
+    pub ui: f32,
+}
+
+impl Default for VolumeBuses {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 1.0,
+            sound_effect: 1.0,
+            ui: 1.0,
+        }
+    }
+}
+
+impl VolumeBuses {
+    /// 一条总线的音量。
+    pub fn get(&self, bus: VolumeBus) -> f32 {
+        match bus {
+            VolumeBus::Master => self.master,
+            VolumeBus::Music => self.music,
+            VolumeBus::SoundEffect => self.sound_effect,
+            VolumeBus::Ui => self.ui,
+        }
+    }
+
+    /// 设置一条总线的音量。
+    pub fn set(&mut self, bus: VolumeBus, volume: f32) {
+        match bus {
+            VolumeBus::Master => self.master = volume,
+            VolumeBus::Music => self.music = volume,
+            VolumeBus::SoundEffect => self.sound_effect = volume,
+            VolumeBus::Ui => self.ui = volume,
+        }
+    }
+
+    /// 某个类别的声音的音量，即主音量乘以类别音量。没有类别的声音只使用主音量。
+    fn category_volume(&self, category: Option<VolumeBus>) -> Volume {
+        let category = category.map_or(1.0, |bus| self.get(bus));
+        Volume::Linear(self.master * category)
+    }
+}
+
+/// 一个组织标记组件，如果它属于一般的“音乐”类别（例如全局背景音乐、原声带），
+/// 应该添加到生成的 [`AudioPlayer`]。音乐应通过 [`AddStateMusic`] 声明，而不是直接生成。
+///
+/// 然后可以用它来查询和操作该类别中的声音。
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Music;
+
+/// 一个组织标记组件，如果它属于一般的“音效”类别（例如脚步声、魔法咒语的声音、门打开的声音），
+/// 应该添加到生成的 [`AudioPlayer`]。音效通常通过 [`PlaySound`] 播放。
+///
+/// 然后可以用它来查询和操作该类别中的声音。
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct SoundEffect;
+
+/// 一个组织标记组件，用于界面的声音（例如按钮的悬停和点击声）。
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct UiSound;
+
+type AudioVolumeQuery<'w, 's, F = ()> = Query<
+    'w,
+    's,
+    (
+        &'static PlaybackSettings,
+        &'static mut AudioSink,
+        Option<&'static MusicFade>,
+        Has<Music>,
+        Has<SoundEffect>,
+        Has<UiSound>,
+    ),
+    F,
+>;
+
+/// 声音开始播放时设置它的音量。
+fn apply_volume_on_play(
+    add: On<Add, AudioSink>,
+    global_volume: Res<GlobalVolume>,
+    buses: Res<VolumeBuses>,
+    mut audio_query: AudioVolumeQuery,
+) {
+    if let Ok(item) = audio_query.get_mut(add.entity) {
+        set_volume(item, &global_volume, &buses);
+    }
+}
+
+/// 音量改变时更新所有正在播放的声音。
+fn update_volume(
+    global_volume: Res<GlobalVolume>,
+    buses: Res<VolumeBuses>,
+    mut audio_query: AudioVolumeQuery,
+) {
+    for item in &mut audio_query {
+        set_volume(item, &global_volume, &buses);
+    }
+}
+
+fn set_volume(
+    (playback, mut sink, fade, music, sound_effect, ui): (
+        &PlaybackSettings,
+        Mut<AudioSink>,
+        Option<&MusicFade>,
+        bool,
+        bool,
+        bool,
+    ),
+    global_volume: &GlobalVolume,
+    buses: &VolumeBuses,
+) {
+    let category = [
+        (music, VolumeBus::Music),
+        (sound_effect, VolumeBus::SoundEffect),
+        (ui, VolumeBus::Ui),
+    ]
+    .into_iter()
+    .find_map(|(is, bus)| is.then_some(bus));
+    let fade = Volume::Linear(fade.map_or(1.0, |fade| fade.gain));
+    sink.set_volume(
+        global_volume.volume * buses.category_volume(category) * playback.volume * fade,
+    );
+}
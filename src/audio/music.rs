@@ -0,0 +1,405 @@
+//! 背景音乐：按状态选择的播放列表和曲目之间的交叉淡入淡出。
+//!
+//! 界面和游戏不直接生成音乐实体，而是通过 [`AddStateMusic`] 声明处于某个状态时播放的曲目，
+//! 状态切换后由这里通过 [`MusicDirector`] 切换音乐，不处于任何注册的状态时停止音乐。
+//! 切换曲目时旧曲目淡出、新曲目淡入；请求的曲目已经在播放时不会重新开始。
+
+use std::time::Duration;
+
+use bevy::{prelude::*, state::state::StateTransitionSystems};
+
+use crate::{
+    AppSystems,
+    audio::{AudioVolumeQuery, Music, VolumeBuses, set_volume},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<MusicDirector>();
+    app.register_type::<MusicTrack>();
+    app.register_type::<MusicFade>();
+    app.register_type::<FadingOut>();
+    app.init_resource::<MusicDirector>();
+    app.init_resource::<MusicMap>();
+
+    // 所有状态切换完成后再选择音乐，这样同一帧中多个状态的变化只会切换一次。
+    app.add_systems(
+        StateTransition,
+        select_state_music.after(StateTransitionSystems::EnterSchedules),
+    );
+
+    app.add_systems(
+        Update,
+        (
+            advance_playlist,
+            switch_track,
+            fade_tracks,
+            apply_fade_volume,
+        )
+            .chain()
+            .in_set(AppSystems::Update),
+    );
+}
+
+pub trait AddStateMusic {
+    /// 处于 `state` 时播放 `tracks`（相对于 `assets` 目录的路径）。
+    /// 多个注册的状态同时处于活动时，使用先注册的状态的音乐。
+    fn add_state_music<S: States>(&mut self, state: S, tracks: &[&'static str]) -> &mut Self;
+}
+
+impl AddStateMusic for App {
+    fn add_state_music<S: States>(&mut self, state: S, tracks: &[&'static str]) -> &mut Self {
+        let assets = self.world().resource::<AssetServer>();
+        let playlist = tracks.iter().map(|&path| assets.load(path)).collect();
+        self.world_mut()
+            .resource_mut::<MusicMap>()
+            .add(state, playlist);
+        self
+    }
+}
+
+/// 一个状态和处于这个状态时的播放列表。
+struct StateMusic {
+    /// 状态当前是否处于活动。
+    active: Box<dyn Fn(&World) -> bool + Send + Sync>,
+    playlist: Vec<Handle<AudioSource>>,
+}
+
+/// 各状态的背景音乐，通过 [`AddStateMusic`] 注册。
+#[derive(Resource, Default)]
+pub struct MusicMap {
+    entries: Vec<StateMusic>,
+    /// 上一次选择的条目，用于只在选择变化时切换音乐。
+    selected: Option<usize>,
+}
+
+impl MusicMap {
+    fn add<S: States>(&mut self, state: S, playlist: Vec<Handle<AudioSource>>) {
+        self.entries.push(StateMusic {
+            active: Box::new(move |world| {
+                world
+                    .get_resource::<State<S>>()
+                    .is_some_and(|current| *current.get() == state)
+            }),
+            playlist,
+        });
+    }
+
+    /// 第一个处于活动的状态的条目。
+    fn select(&self, world: &World) -> Option<usize> {
+        self.entries.iter().position(|entry| (entry.active)(world))
+    }
+}
+
+/// 状态切换后，播放 [`MusicMap`] 中第一个处于活动的状态的音乐。
+fn select_state_music(world: &mut World) {
+    world.resource_scope(|world, mut map: Mut<MusicMap>| {
+        let selected = map.select(world);
+        if selected == map.selected {
+            return;
+        }
+        map.selected = selected;
+        let playlist = selected.map_or_else(Vec::new, |index| map.entries[index].playlist.clone());
+        world
+            .resource_mut::<MusicDirector>()
+            .play_playlist(playlist);
+    });
+}
+
+/// 决定播放哪一首音乐。
+///
+/// 播放列表只有一首曲目时循环播放它，有多首曲目时依次播放并在结束后回到第一首。
+/// 循环播放的曲目不会结束，因此播放列表在一首和多首之间变化时，当前曲目会淡出后重新开始。
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct MusicDirector {
+    /// 切换曲目时交叉淡入淡出的时长。
+    pub fade_duration: Duration,
+    playlist: Vec<Handle<AudioSource>>,
+    /// 当前曲目在播放列表中的序号。
+    index: usize,
+}
+
+impl Default for MusicDirector {
+    fn default() -> Self {
+        Self {
+            fade_duration: Duration::from_secs(2),
+            playlist: Vec::new(),
+            index: 0,
+        }
+    }
+}
+
+impl MusicDirector {
+    /// 依次播放一个播放列表，空的播放列表会淡出并停止音乐。如果当前曲目也在新的播放列表中，
+    /// 它会继续播放，之后从它在新列表中的位置继续。
+    pub fn play_playlist(&mut self, playlist: Vec<Handle<AudioSource>>) {
+        if playlist == self.playlist {
+            return;
+        }
+        self.index = self
+            .current()
+            .and_then(|current| playlist.iter().position(|track| track == current))
+            .unwrap_or(0);
+        self.playlist = playlist;
+    }
+
+    /// 当前应该播放的曲目。
+    pub fn current(&self) -> Option<&Handle<AudioSource>> {
+        self.playlist.get(self.index)
+    }
+
+    /// 当前曲目是否循环播放。
+    fn looping(&self) -> bool {
+        self.playlist.len() == 1
+    }
+
+    /// 当前曲目播放结束，切换到下一首。
+    fn advance(&mut self) {
+        if !self.playlist.is_empty() {
+            self.index = (self.index + 1) % self.playlist.len();
+        }
+    }
+}
+
+/// 由 [`MusicDirector`] 生成的音乐实体，记录它播放的曲目。
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct MusicTrack {
+    track: Handle<AudioSource>,
+    /// 是否循环播放，见 [`MusicDirector::looping`]。
+    looping: bool,
+}
+
+/// 音乐淡入淡出的增益（从 0 到 1），与音量总线的音量相乘。
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub(super) struct MusicFade {
+    pub gain: f32,
+}
+
+impl MusicFade {
+    /// 把增益向淡入或淡出的方向改变 `step`，返回淡出是否已经结束。
+    fn step(&mut self, step: f32, fading_out: bool) -> bool {
+        if fading_out {
+            self.gain = (self.gain - step).max(0.0);
+            return self.gain == 0.0;
+        }
+        if self.gain < 1.0 {
+            self.gain = (self.gain + step).min(1.0);
+        }
+        false
+    }
+}
+
+/// 标记正在淡出的音乐实体，淡出结束后删除。
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct FadingOut;
+
+/// 不在淡出中的音乐实体，即正在播放的当前曲目。
+type ActiveTrack = (With<MusicTrack>, Without<FadingOut>);
+
+/// 当前曲目播放结束后，切换到播放列表中的下一首。
+fn advance_playlist(
+    mut commands: Commands,
+    mut director: ResMut<MusicDirector>,
+    track_query: Query<(Entity, &AudioSink), ActiveTrack>,
+) {
+    for (entity, sink) in &track_query {
+        if sink.empty() {
+            commands.entity(entity).despawn();
+            director.advance();
+        }
+    }
+}
+
+/// 当前曲目与 [`MusicDirector`] 请求的不同时，淡出旧曲目并淡入新曲目。
+fn switch_track(
+    mut commands: Commands,
+    director: Res<MusicDirector>,
+    track_query: Query<(Entity, &MusicTrack, Has<AudioSink>), ActiveTrack>,
+) {
+    let requested = director.current();
+    let looping = director.looping();
+    let mut playing = false;
+    for (entity, track, has_sink) in &track_query {
+        if Some(&track.track) == requested && track.looping == looping && !playing {
+            playing = true;
+        } else if has_sink {
+            commands.entity(entity).insert(FadingOut);
+        } else {
+            // 还没有开始播放的曲目直接删除。
+            commands.entity(entity).despawn();
+        }
+    }
+    if playing {
+        return;
+    }
+    if let Some(track) = requested {
+        commands.spawn(music_track(track.clone(), looping));
+    }
+}
+
+/// 从静音开始淡入播放 `track` 的音乐实体。
+fn music_track(track: Handle<AudioSource>, looping: bool) -> impl Bundle {
+    (
+        Name::new("Music"),
+        AudioPlayer(track.clone()),
+        if looping {
+            PlaybackSettings::LOOP
+        } else {
+            PlaybackSettings::ONCE
+        },
+        Music,
+        MusicTrack { track, looping },
+        MusicFade { gain: 0.0 },
+    )
+}
+
+/// 更新淡入淡出的增益，删除淡出结束的曲目。
+fn fade_tracks(
+    mut commands: Commands,
+    time: Res<Time>,
+    director: Res<MusicDirector>,
+    mut fade_query: Query<(Entity, &mut MusicFade, Has<FadingOut>), With<AudioSink>>,
+) {
+    let step = if director.fade_duration.is_zero() {
+        1.0
+    } else {
+        time.delta_secs() / director.fade_duration.as_secs_f32()
+    };
+    for (entity, mut fade, fading_out) in &mut fade_query {
+        if (fading_out || fade.gain < 1.0) && fade.step(step, fading_out) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// 把改变的增益应用到正在播放的音乐上。
+fn apply_fade_volume(
+    global_volume: Res<GlobalVolume>,
+    buses: Res<VolumeBuses>,
+    mut audio_query: AudioVolumeQuery<Changed<MusicFade>>,
+) {
+    for item in &mut audio_query {
+        set_volume(item, &global_volume, &buses);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{asset::uuid_handle, state::app::StatesPlugin};
+
+    use super::*;
+
+    const A: Handle<AudioSource> = uuid_handle!("5d3b6a1e-3f0c-4d5e-9a8b-1c2d3e4f5a6b");
+    const B: Handle<AudioSource> = uuid_handle!("0f6e5d4c-3b2a-4918-8776-655443322110");
+
+    #[test]
+    fn test_play_playlist() {
+        let mut director = MusicDirector::default();
+
+        director.play_playlist(vec![A, B]);
+        assert_eq!(director.current(), Some(&A));
+        assert!(!director.looping());
+        director.advance();
+        assert_eq!(director.current(), Some(&B));
+        // 当前曲目在新的播放列表中时继续播放。
+        director.play_playlist(vec![B]);
+        assert_eq!(director.current(), Some(&B));
+        assert!(director.looping());
+        director.play_playlist(vec![A, B]);
+        assert_eq!(director.current(), Some(&B));
+        director.advance();
+        assert_eq!(director.current(), Some(&A));
+        director.play_playlist(Vec::new());
+        assert_eq!(director.current(), None);
+    }
+
+    #[test]
+    fn test_fade() {
+        let mut fade = MusicFade { gain: 0.0 };
+        assert!(!fade.step(0.375, false));
+        assert!(!fade.step(0.375, false));
+        assert_eq!(fade.gain, 0.75);
+        // 淡入不会超过 1。
+        assert!(!fade.step(0.375, false));
+        assert_eq!(fade.gain, 1.0);
+        assert!(!fade.step(0.625, true));
+        assert_eq!(fade.gain, 0.375);
+        // 增益降到 0 时淡出结束。
+        assert!(fade.step(0.625, true));
+        assert_eq!(fade.gain, 0.0);
+    }
+
+    #[derive(States, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    enum TestState {
+        #[default]
+        Silent,
+        Menu,
+        Game,
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin));
+        app.init_state::<TestState>();
+        app.init_resource::<MusicDirector>();
+        app.init_resource::<MusicMap>();
+        app.add_systems(
+            StateTransition,
+            select_state_music.after(StateTransitionSystems::EnterSchedules),
+        );
+        app.add_systems(Update, switch_track);
+        let mut map = app.world_mut().resource_mut::<MusicMap>();
+        map.add(TestState::Menu, vec![A]);
+        map.add(TestState::Game, vec![A, B]);
+        app
+    }
+
+    fn set_state(app: &mut App, state: TestState) {
+        app.world_mut()
+            .resource_mut::<NextState<TestState>>()
+            .set(state);
+        app.update();
+    }
+
+    /// 所有音乐实体播放的曲目和是否循环播放。
+    fn tracks(app: &mut App) -> Vec<(Handle<AudioSource>, bool)> {
+        let world = app.world_mut();
+        world
+            .query::<&MusicTrack>()
+            .iter(world)
+            .map(|track| (track.track.clone(), track.looping))
+            .collect()
+    }
+
+    #[test]
+    fn test_state_music() {
+        let mut app = app();
+        app.update();
+        assert_eq!(app.world().resource::<MusicDirector>().current(), None);
+        assert!(tracks(&mut app).is_empty());
+
+        // 只有一首曲目的状态循环播放它。
+        set_state(&mut app, TestState::Menu);
+        assert_eq!(tracks(&mut app), [(A, true)]);
+
+        // 曲目相同但播放列表不再只有一首时重新开始，不再循环播放。
+        set_state(&mut app, TestState::Game);
+        assert_eq!(app.world().resource::<MusicDirector>().current(), Some(&A));
+        assert_eq!(tracks(&mut app), [(A, false)]);
+
+        // 手动播放的音乐在下一次状态变化前保持不变。
+        app.world_mut()
+            .resource_mut::<MusicDirector>()
+            .play_playlist(vec![B]);
+        app.update();
+        assert_eq!(tracks(&mut app), [(B, true)]);
+
+        // 离开所有注册的状态时停止音乐。
+        set_state(&mut app, TestState::Silent);
+        assert_eq!(app.world().resource::<MusicDirector>().current(), None);
+        assert!(tracks(&mut app).is_empty());
+    }
+}
@@ -0,0 +1,294 @@
+//! 音效：按名称播放数据文件中定义的音效。
+//!
+//! 音效定义（cue）从 `assets/sounds.ron` 加载，每个音效包含若干候选声音文件和播放参数。
+//! 游戏和界面发送 [`PlaySound`] 消息请求播放，这里负责随机选择声音、随机变化音高和音量，
+//! 并限制同一音效同时播放的数量和两次播放之间的最短间隔，避免快速悬停按钮时声音叠在一起。
+
+use std::time::Duration;
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    audio::Volume,
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use rand::{Rng, seq::SliceRandom};
+use serde::Deserialize;
+
+use crate::{
+    asset_tracking::{LoadResource, RonAssetLoaderError},
+    audio::{SoundEffect, UiSound, cues},
+};
+
+/// 音效定义文件的路径。
+const SOUND_CUES_PATH: &str = "sounds.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<SoundCues>();
+    app.register_type::<SoundInstance>();
+    app.add_message::<PlaySound>();
+    app.register_asset_loader(SoundCueLoader);
+    app.load_resource_from_path::<SoundCues>(SOUND_CUES_PATH);
+
+    app.add_systems(PostUpdate, play_sounds.run_if(resource_exists::<SoundCues>));
+    app.add_systems(
+        Update,
+        check_cue_references.run_if(resource_exists_and_changed::<SoundCues>),
+    );
+}
+
+/// 请求播放一个音效，参数是音效的名称（[`SoundCueDef::id`]），见 [`cues`]。
+#[derive(Message, Debug, Clone, Copy)]
+pub struct PlaySound(pub &'static str);
+
+/// 音效所属的音量总线。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect, Deserialize)]
+pub enum SoundCategory {
+    #[default]
+    SoundEffect,
+    Ui,
+}
+
+/// 数据文件中的一个音效定义。
+#[derive(Clone, Debug, Reflect, Deserialize)]
+#[serde(default)]
+pub struct SoundCueDef {
+    /// 音效的唯一名称。
+    pub id: String,
+    /// 候选的声音文件路径（相对于 `assets` 目录），每次播放随机选择一个。
+    pub sounds: Vec<String>,
+    pub category: SoundCategory,
+    /// 基础音量（线性）。
+    pub volume: f32,
+    /// 音量的随机变化幅度，例如 0.1 表示在基础音量的 ±10% 之间变化。
+    pub volume_variation: f32,
+    /// 音高（播放速度）的随机变化幅度。
+    pub pitch_variation: f32,
+    /// 同时播放的最大数量，达到后忽略新的请求。
+    pub max_instances: usize,
+    /// 两次播放之间的最短间隔（秒）。
+    pub cooldown: f32,
+}
+
+impl Default for SoundCueDef {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            sounds: Vec::new(),
+            category: SoundCategory::default(),
+            volume: 1.0,
+            volume_variation: 0.0,
+            pitch_variation: 0.0,
+            max_instances: 4,
+            cooldown: 0.0,
+        }
+    }
+}
+
+/// 音效定义文件的内容。
+#[derive(Deserialize)]
+struct SoundCueFile {
+    cues: Vec<SoundCueDef>,
+}
+
+impl SoundCueFile {
+    fn validate(&self) -> Result<(), String> {
+        let mut ids = HashSet::new();
+        for (index, cue) in self.cues.iter().enumerate() {
+            if cue.id.is_empty() {
+                return Err(format!("sound cue #{index} has an empty `id`"));
+            }
+            if !ids.insert(cue.id.as_str()) {
+                return Err(format!("duplicate sound cue id `{}`", cue.id));
+            }
+            if cue.sounds.is_empty() {
+                return Err(format!("sound cue `{}` has no `sounds`", cue.id));
+            }
+            if cue.max_instances == 0 {
+                return Err(format!(
+                    "sound cue `{}` must have a positive `max_instances`",
+                    cue.id
+                ));
+            }
+            if cue.volume < 0.0
+                || !(0.0..1.0).contains(&cue.volume_variation)
+                || !(0.0..1.0).contains(&cue.pitch_variation)
+                || cue.cooldown < 0.0
+            {
+                return Err(format!(
+                    "sound cue `{}` has a negative volume or cooldown, or a variation outside [0, 1)",
+                    cue.id
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 一个音效定义和它的声音。
+#[derive(Clone, Debug, Reflect)]
+pub struct SoundCue {
+    pub def: SoundCueDef,
+    pub sounds: Vec<Handle<AudioSource>>,
+}
+
+/// 所有音效，按名称索引。
+#[derive(Resource, Asset, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct SoundCues {
+    pub cues: HashMap<String, SoundCue>,
+}
+
+/// 加载音效定义文件，并把其中的声音文件作为依赖一起加载，
+/// 这样加载界面结束前所有音效都已经可以播放。
+struct SoundCueLoader;
+
+impl AssetLoader for SoundCueLoader {
+    type Asset = SoundCues;
+    type Settings = ();
+    type Error = RonAssetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: SoundCueFile = ron::de::from_bytes(&bytes)?;
+        file.validate().map_err(RonAssetLoaderError::Invalid)?;
+        let cues = file
+            .cues
+            .into_iter()
+            .map(|def| {
+                let sounds = def
+                    .sounds
+                    .iter()
+                    .map(|path| load_context.load(path))
+                    .collect();
+                (def.id.clone(), SoundCue { def, sounds })
+            })
+            .collect();
+        Ok(SoundCues { cues })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["sounds.ron"]
+    }
+}
+
+/// 由 [`PlaySound`] 生成的正在播放的音效，记录它属于哪个音效。
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct SoundInstance(String);
+
+fn play_sounds(
+    mut commands: Commands,
+    mut play_reader: MessageReader<PlaySound>,
+    cues: Res<SoundCues>,
+    time: Res<Time<Real>>,
+    mut last_played: Local<HashMap<&'static str, Duration>>,
+    instance_query: Query<&SoundInstance>,
+) {
+    if play_reader.is_empty() {
+        return;
+    }
+    let mut instances = HashMap::<&str, usize>::new();
+    for SoundInstance(id) in &instance_query {
+        *instances.entry(id.as_str()).or_default() += 1;
+    }
+    let now = time.elapsed();
+    let rng = &mut rand::thread_rng();
+
+    for &PlaySound(id) in play_reader.read() {
+        let Some(cue) = cues.cues.get(id) else {
+            warn!("Unknown sound cue `{id}`");
+            continue;
+        };
+        let def = &cue.def;
+        let count = instances.entry(id).or_default();
+        if *count >= def.max_instances {
+            continue;
+        }
+        if let Some(last) = last_played.get(id)
+            && now.saturating_sub(*last).as_secs_f32() < def.cooldown
+        {
+            continue;
+        }
+        let Some(sound) = cue.sounds.choose(rng) else {
+            continue;
+        };
+        *count += 1;
+        last_played.insert(id, now);
+
+        let volume = def.volume * (1.0 + vary(rng, def.volume_variation));
+        let speed = 1.0 + vary(rng, def.pitch_variation);
+        let playback = PlaybackSettings::DESPAWN
+            .with_volume(Volume::Linear(volume))
+            .with_speed(speed);
+        let mut entity = commands.spawn((
+            Name::new(format!("Sound {id}")),
+            AudioPlayer(sound.clone()),
+            playback,
+            SoundInstance(def.id.clone()),
+        ));
+        match def.category {
+            SoundCategory::SoundEffect => entity.insert(SoundEffect),
+            SoundCategory::Ui => entity.insert(UiSound),
+        };
+    }
+}
+
+/// `[-variation, variation]` 之间的随机数。
+fn vary(rng: &mut impl Rng, variation: f32) -> f32 {
+    if variation > 0.0 {
+        rng.gen_range(-variation..=variation)
+    } else {
+        0.0
+    }
+}
+
+/// 音效文件缺少代码中使用的音效时发出警告。
+fn check_cue_references(sound_cues: Res<SoundCues>) {
+    for id in cues::ALL {
+        if !sound_cues.cues.contains_key(*id) {
+            warn!("Sound cue `{id}` is not defined in `{SOUND_CUES_PATH}`");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_sound_cues() {
+        let file: SoundCueFile =
+            ron::from_str(r#"(cues: [(id: "step", sounds: ["step1.ogg"], pitch_variation: 0.1)])"#)
+                .unwrap();
+        assert!(file.validate().is_ok());
+        assert_eq!(file.cues[0].max_instances, 4);
+        assert_eq!(file.cues[0].category, SoundCategory::SoundEffect);
+
+        let file: SoundCueFile = ron::from_str(r#"(cues: [(id: "step")])"#).unwrap();
+        assert!(file.validate().is_err());
+        let file: SoundCueFile = ron::from_str(
+            r#"(cues: [(id: "step", sounds: ["step1.ogg"], volume_variation: 1.5)])"#,
+        )
+        .unwrap();
+        assert!(file.validate().is_err());
+    }
+
+    #[test]
+    fn test_sound_cue_file() {
+        let file: SoundCueFile = ron::from_str(include_str!("../../assets/sounds.ron")).unwrap();
+        assert_eq!(file.validate(), Ok(()));
+        for id in cues::ALL {
+            assert!(
+                file.cues.iter().any(|cue| cue.id == *id),
+                "sound cue `{id}` is not defined"
+            );
+        }
+    }
+}
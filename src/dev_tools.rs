@@ -4,7 +4,10 @@ use bevy::{
     dev_tools::states::log_transitions, input::common_conditions::input_just_pressed, prelude::*,
 };
 
-use crate::screens::Screen;
+use crate::{
+    i18n::{LanguageRes, locale::Locales},
+    screens::Screen,
+};
 
 pub(super) fn plugin(app: &mut App) {
     // 记录 `Screen` 状态的转换。
@@ -15,10 +18,36 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         toggle_debug_ui.run_if(input_just_pressed(TOGGLE_KEY)),
     );
+
+    // 列出各语言缺少的文本，在文本加载后和按下按键时。
+    app.add_systems(
+        PostUpdate,
+        report_missing_texts.run_if(
+            resource_exists_and_changed::<Locales>.or(input_just_pressed(REPORT_MISSING_KEY)),
+        ),
+    );
 }
 
 const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
 
+const REPORT_MISSING_KEY: KeyCode = KeyCode::F8;
+
 fn toggle_debug_ui(mut options: ResMut<UiDebugOptions>) {
     options.toggle();
 }
+
+/// 与默认语言相比，记录每种语言缺少的文本 Key。
+fn report_missing_texts(lang_res: Res<LanguageRes>) {
+    for language in &lang_res.language_list {
+        let missing = lang_res.missing_keys(language);
+        if missing.is_empty() {
+            info!("Language {language} has all texts");
+        } else {
+            warn!(
+                "Language {language} lacks {} texts: {}",
+                missing.len(),
+                missing.join(", ")
+            );
+        }
+    }
+}
@@ -5,18 +5,21 @@
 //! - [计时器](https://github.com/bevyengine/bevy/blob/latest/examples/time/timers.rs)
 
 use bevy::prelude::*;
-use rand::prelude::*;
 use std::time::Duration;
 
 use crate::{
     AppSystems, PausableSystems,
-    audio::sound_effect,
-    games::{movement::MovementController, player::PlayerAssets},
+    audio::{PlaySound, cues},
+    games::{
+        movement::MovementController,
+        player::{PlayerAssets, ToolBelt},
+    },
+    save::RegisterSaved,
 };
 
 pub(super) fn plugin(app: &mut App) {
     // 根据控制进行动画和播放音效。
-    app.register_type::<PlayerAnimation>();
+    app.save_component::<PlayerAnimation>();
     app.add_systems(
         Update,
         (
@@ -34,17 +37,24 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-/// 更新精灵方向和动画状态（静止/行走）。
+/// 更新精灵方向和动画状态（静止/行走/使用工具）。
 fn update_animation_movement(
-    mut player_query: Query<(&MovementController, &mut Sprite, &mut PlayerAnimation)>,
+    mut player_query: Query<(
+        &MovementController,
+        Option<&ToolBelt>,
+        &mut Sprite,
+        &mut PlayerAnimation,
+    )>,
 ) {
-    for (controller, mut sprite, mut animation) in &mut player_query {
+    for (controller, tool_belt, mut sprite, mut animation) in &mut player_query {
         let dx = controller.intent.x;
         if dx != 0.0 {
             sprite.flip_x = dx < 0.0;
         }
 
-        let animation_state = if controller.intent == Vec2::ZERO {
+        let animation_state = if tool_belt.is_some_and(ToolBelt::is_busy) {
+            PlayerAnimationState::UsingTool
+        } else if controller.intent == Vec2::ZERO {
             PlayerAnimationState::Idling
         } else {
             PlayerAnimationState::Walking
@@ -74,8 +84,7 @@ fn update_animation_atlas(mut query: Query<(&PlayerAnimation, &mut Sprite)>) {
 
 /// 如果玩家在移动，则播放与动画同步的脚步声效果。
 fn trigger_step_sound_effect(
-    mut commands: Commands,
-    player_assets: Res<PlayerAssets>,
+    mut sound_writer: MessageWriter<PlaySound>,
     mut step_query: Query<&PlayerAnimation>,
 ) {
     for animation in &mut step_query {
@@ -83,9 +92,7 @@ fn trigger_step_sound_effect(
             && animation.changed()
             && (animation.frame == 2 || animation.frame == 5)
         {
-            let rng = &mut rand::thread_rng();
-            let random_step = player_assets.steps.choose(rng).unwrap().clone();
-            commands.spawn(sound_effect(random_step));
+            sound_writer.write(PlaySound(cues::STEP));
         }
     }
 }
@@ -104,6 +111,7 @@ pub struct PlayerAnimation {
 pub enum PlayerAnimationState {
     Idling,
     Walking,
+    UsingTool,
 }
 
 impl PlayerAnimation {
@@ -115,6 +123,10 @@ impl PlayerAnimation {
     const WALKING_FRAMES: usize = 6;
     /// 每帧行走的持续时间。
     const WALKING_INTERVAL: Duration = Duration::from_millis(50);
+    /// 使用工具时依次显示的行走帧。图集中没有专门的动作帧，因此复用行走帧。
+    const TOOL_FRAMES: [usize; 4] = [1, 2, 4, 5];
+    /// 使用工具时每帧的持续时间。
+    const TOOL_INTERVAL: Duration = Duration::from_millis(100);
 
     fn idling() -> Self {
         Self {
@@ -132,6 +144,14 @@ impl PlayerAnimation {
         }
     }
 
+    fn using_tool() -> Self {
+        Self {
+            timer: Timer::new(Self::TOOL_INTERVAL, TimerMode::Repeating),
+            frame: 0,
+            state: PlayerAnimationState::UsingTool,
+        }
+    }
+
     pub fn new() -> Self {
         Self::idling()
     }
@@ -146,6 +166,7 @@ impl PlayerAnimation {
             % match self.state {
                 PlayerAnimationState::Idling => Self::IDLE_FRAMES,
                 PlayerAnimationState::Walking => Self::WALKING_FRAMES,
+                PlayerAnimationState::UsingTool => Self::TOOL_FRAMES.len(),
             };
     }
 
@@ -155,6 +176,7 @@ impl PlayerAnimation {
             match state {
                 PlayerAnimationState::Idling => *self = Self::idling(),
                 PlayerAnimationState::Walking => *self = Self::walking(),
+                PlayerAnimationState::UsingTool => *self = Self::using_tool(),
             }
         }
     }
@@ -169,6 +191,7 @@ impl PlayerAnimation {
         match self.state {
             PlayerAnimationState::Idling => self.frame,
             PlayerAnimationState::Walking => 6 + self.frame,
+            PlayerAnimationState::UsingTool => 6 + Self::TOOL_FRAMES[self.frame],
         }
     }
 }
@@ -0,0 +1,211 @@
+//! 游戏内的时钟和日历。
+//!
+//! [`GameClock`] 只在游戏未暂停时前进，每真实秒前进 [`GameClock::time_scale`] 分钟。
+//! 每天开始时发送 [`DayStarted`] 消息，每季第一天还会先发送 [`SeasonChanged`] 消息。
+//! 作物生长、商店营业时间和音乐选择等都可以读取时钟或响应这些消息，
+//! 随游戏时间变化的数值使用 [`GameClock::delta_minutes`] 前进，这样会跟随时间流速和暂停。
+//!
+//! 时钟在每次进入 [`Screen::Gameplay`] 时重置为第 1 天春季的早上。
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    AppSystems, PausableSystems,
+    i18n::config::{SEASON_AUTUMN, SEASON_SPRING, SEASON_SUMMER, SEASON_WINTER},
+    save::RegisterSaved,
+    screens::Screen,
+};
+
+/// 一天的分钟数。
+const MINUTES_PER_DAY: f32 = 24.0 * 60.0;
+/// 每季的天数。
+const DAYS_PER_SEASON: u32 = 28;
+/// 每局开始的时刻（分钟）。
+const START_MINUTE: f32 = 6.0 * 60.0;
+/// 默认每真实秒前进的游戏分钟数，一天约为 2.4 分钟。
+const DEFAULT_TIME_SCALE: f32 = 10.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.save_resource::<GameClock>();
+    app.init_resource::<GameClock>();
+    app.add_message::<DayStarted>();
+    app.add_message::<SeasonChanged>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), reset_clock);
+    app.add_systems(
+        Update,
+        advance_clock
+            .in_set(AppSystems::TickTimers)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// 季节。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect, Deserialize)]
+pub enum Season {
+    #[default]
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    /// 下一个季节。
+    pub fn next(self) -> Self {
+        match self {
+            Self::Spring => Self::Summer,
+            Self::Summer => Self::Autumn,
+            Self::Autumn => Self::Winter,
+            Self::Winter => Self::Spring,
+        }
+    }
+
+    /// 季节名称的文本键。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Spring => SEASON_SPRING,
+            Self::Summer => SEASON_SUMMER,
+            Self::Autumn => SEASON_AUTUMN,
+            Self::Winter => SEASON_WINTER,
+        }
+    }
+}
+
+/// 游戏内的时间。
+#[derive(Resource, Reflect, Clone, Debug)]
+#[reflect(Resource)]
+pub struct GameClock {
+    /// 当天已经过去的分钟数。
+    minutes: f32,
+    /// 从 1 开始的天数。
+    day: u32,
+    season: Season,
+    /// 每真实秒前进的游戏分钟数。
+    pub time_scale: f32,
+    /// 这一帧前进的游戏分钟数，不保存。
+    #[reflect(ignore)]
+    delta: f32,
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self {
+            minutes: START_MINUTE,
+            day: 1,
+            season: Season::default(),
+            time_scale: DEFAULT_TIME_SCALE,
+            delta: 0.0,
+        }
+    }
+}
+
+impl GameClock {
+    /// 从 1 开始的天数。
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// 当前季节中的第几天，从 1 开始。
+    pub fn day_of_season(&self) -> u32 {
+        (self.day - 1) % DAYS_PER_SEASON + 1
+    }
+
+    pub fn season(&self) -> Season {
+        self.season
+    }
+
+    /// 当前的小时（0-23）。
+    pub fn hour(&self) -> u32 {
+        self.minutes as u32 / 60
+    }
+
+    /// 当前小时内的分钟（0-59）。
+    pub fn minute(&self) -> u32 {
+        self.minutes as u32 % 60
+    }
+
+    /// 这一帧前进的游戏分钟数。游戏暂停时时钟不前进，读取它的系统也应该是 [`PausableSystems`]，
+    /// 并在 [`AppSystems::TickTimers`] 之后运行。
+    pub fn delta_minutes(&self) -> f32 {
+        self.delta
+    }
+
+    /// 前进 `minutes` 游戏分钟，返回期间开始的每一天、其季节以及季节是否发生了变化。
+    fn advance(&mut self, minutes: f32) -> Vec<(u32, Season, bool)> {
+        let mut started = Vec::new();
+        self.delta = minutes;
+        self.minutes += minutes;
+        while self.minutes >= MINUTES_PER_DAY {
+            self.minutes -= MINUTES_PER_DAY;
+            self.day += 1;
+            let season_changed = self.day_of_season() == 1;
+            if season_changed {
+                self.season = self.season.next();
+            }
+            started.push((self.day, self.season, season_changed));
+        }
+        started
+    }
+}
+
+/// 新的一天开始了。
+#[derive(Message, Clone, Copy, Debug)]
+pub struct DayStarted {
+    /// 从 1 开始的天数。
+    pub day: u32,
+}
+
+/// 进入了新的季节，在同一天的 [`DayStarted`] 之前发送。
+#[derive(Message, Clone, Copy, Debug)]
+pub struct SeasonChanged {
+    pub season: Season,
+}
+
+fn reset_clock(mut clock: ResMut<GameClock>) {
+    // 保留游戏设置的时间流速。
+    *clock = GameClock {
+        time_scale: clock.time_scale,
+        ..default()
+    };
+}
+
+fn advance_clock(
+    time: Res<Time>,
+    mut clock: ResMut<GameClock>,
+    mut day_started: MessageWriter<DayStarted>,
+    mut season_changed: MessageWriter<SeasonChanged>,
+) {
+    let minutes = time.delta_secs() * clock.time_scale;
+    for (day, season, changed) in clock.advance(minutes) {
+        if changed {
+            season_changed.write(SeasonChanged { season });
+        }
+        day_started.write(DayStarted { day });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_rolls_over() {
+        let mut clock = GameClock::default();
+        assert_eq!((clock.hour(), clock.minute()), (6, 0));
+        assert!(clock.advance(17.0 * 60.0 + 59.0).is_empty());
+        assert_eq!((clock.hour(), clock.minute()), (23, 59));
+
+        let started = clock.advance(1.0);
+        assert_eq!(started, vec![(2, Season::Spring, false)]);
+        assert_eq!(clock.delta_minutes(), 1.0);
+        assert_eq!((clock.day(), clock.hour()), (2, 0));
+
+        let started = clock.advance(MINUTES_PER_DAY * (DAYS_PER_SEASON - 1) as f32);
+        assert_eq!(started.len(), DAYS_PER_SEASON as usize - 1);
+        assert_eq!(started.last(), Some(&(29, Season::Summer, true)));
+        assert_eq!((clock.season(), clock.day_of_season()), (Season::Summer, 1));
+    }
+}
@@ -0,0 +1,513 @@
+//! 作物的生长。
+//!
+//! 作物的种类和生长阶段在 `assets/levels/farming.crops.ron` 中定义。种下的作物只有在土壤湿润
+//! 且处于作物的生长季节时才会生长，每个阶段生长足够的时间后进入下一阶段，最后一个阶段表示已经成熟可以收获。
+//! 土壤干燥太久或进入作物不能生长的季节时作物会枯萎，枯萎的作物无法再生长。
+//! 所有时长都以游戏分钟计算（见 [`GameClock`]），因此会跟随时钟的时间流速。
+//!
+//! 作物的精灵来自一张纹理图集：每种作物占一行，每个生长阶段和枯萎状态各占一列。
+
+use bevy::{
+    image::{ImageLoaderSettings, ImageSampler},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{
+    AppSystems, PausableSystems,
+    asset_tracking::{LoadResource, RonAsset, RonAssetLoader},
+    games::{
+        clock::{GameClock, Season, SeasonChanged},
+        farming::{
+            Farming,
+            field::{FarmField, Soil},
+        },
+        inventory::ItemData,
+        mini_game::{MiniGame, RunStats},
+    },
+    i18n::config::FARMING_CROPS_HARVESTED,
+    save::{RegisterSaved, Saved},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.save_component::<Crop>();
+    app.register_type::<CropData>();
+    app.add_message::<CropHarvested>();
+    app.register_asset_loader(RonAssetLoader::<CropData>::default());
+    app.load_resource_from_path::<CropData>("levels/farming.crops.ron");
+
+    app.register_type::<CropAssets>();
+    app.load_resource::<CropAssets>();
+
+    app.add_systems(
+        Update,
+        check_crop_references
+            .run_if(resource_exists::<ItemData>.and(resource_exists_and_changed::<CropData>)),
+    );
+    app.add_systems(
+        OnEnter(Farming::state()),
+        init_crop_atlas.run_if(resource_exists::<CropData>),
+    );
+    app.add_systems(OnExit(Farming::state()), remove_crop_atlas);
+    app.add_systems(
+        Update,
+        (
+            (grow_crops, update_crop_sprites)
+                .chain()
+                .run_if(resource_exists::<CropData>.and(resource_exists::<CropAtlas>))
+                .in_set(AppSystems::Update)
+                .in_set(PausableSystems),
+            record_harvests.run_if(on_message::<CropHarvested>),
+            wither_out_of_season
+                .run_if(resource_exists::<CropData>.and(on_message::<SeasonChanged>))
+                .in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Farming::state()).and(resource_exists::<FarmField>)),
+    );
+}
+
+/// 作物文件的内容。
+#[derive(Resource, Asset, Clone, Reflect, Deserialize)]
+#[reflect(Resource)]
+pub struct CropData {
+    /// 纹理图集中每行的格子数。
+    pub atlas_columns: u32,
+    /// 浇一次水后土壤保持湿润的时间（游戏分钟）。
+    pub water_duration: f32,
+    /// 所有作物。
+    pub crops: Vec<CropDef>,
+}
+
+/// 一种作物的属性。
+#[derive(Clone, Debug, Reflect, Deserialize)]
+pub struct CropDef {
+    /// 作物的唯一标识。
+    pub id: String,
+    /// 播种时消耗的种子物品标识。
+    pub seed: String,
+    /// 作物在纹理图集中所在的行。
+    pub atlas_row: u32,
+    /// 生长阶段，最后一个阶段表示已经成熟。
+    pub stages: Vec<GrowthStage>,
+    /// 枯萎时使用的纹理图集列。
+    pub withered_column: u32,
+    /// 土壤连续干燥多少游戏分钟后作物枯萎。
+    pub wither_after: f32,
+    /// 作物可以生长的季节，为空表示所有季节都可以生长。其他季节中作物不会生长，
+    /// 进入这样的季节时已经种下的作物会枯萎。
+    #[serde(default)]
+    pub seasons: Vec<Season>,
+    /// 收获时获得的物品。
+    pub harvest: HarvestDef,
+}
+
+/// 一个生长阶段。
+#[derive(Clone, Debug, Reflect, Deserialize)]
+pub struct GrowthStage {
+    /// 该阶段使用的纹理图集列。
+    pub column: u32,
+    /// 进入下一阶段前需要在湿润的土壤中生长的时间（游戏分钟）。最后一个阶段不使用此值。
+    #[serde(default)]
+    pub duration: f32,
+}
+
+/// 收获获得的物品。
+#[derive(Clone, Debug, Reflect, Deserialize)]
+pub struct HarvestDef {
+    /// 物品标识。
+    pub item: String,
+    /// 物品数量。
+    pub count: u32,
+}
+
+impl CropData {
+    /// 返回具有该标识的作物。
+    pub fn get(&self, id: &str) -> Option<&CropDef> {
+        self.crops.iter().find(|crop| crop.id == id)
+    }
+
+    /// 返回由该种子种出的作物。
+    pub fn by_seed(&self, seed: &str) -> Option<&CropDef> {
+        self.crops.iter().find(|crop| crop.seed == seed)
+    }
+
+    /// 纹理图集的行数。
+    fn atlas_rows(&self) -> u32 {
+        self.crops
+            .iter()
+            .map(|crop| crop.atlas_row + 1)
+            .max()
+            .unwrap_or(1)
+    }
+}
+
+impl CropDef {
+    /// 作物在该季节能否生长。
+    pub fn grows_in(&self, season: Season) -> bool {
+        self.seasons.is_empty() || self.seasons.contains(&season)
+    }
+
+    /// 纹理图集中的索引。
+    fn atlas_index(&self, crop: &Crop, columns: u32) -> usize {
+        let column = if crop.withered {
+            self.withered_column
+        } else {
+            self.stages[crop.stage].column
+        };
+        (self.atlas_row * columns + column) as usize
+    }
+}
+
+impl RonAsset for CropData {
+    const EXTENSIONS: &'static [&'static str] = &["crops.ron"];
+
+    fn validate(&self) -> Result<(), String> {
+        if self.atlas_columns == 0 {
+            return Err("`atlas_columns` must be positive".to_string());
+        }
+        if self.water_duration <= 0.0 {
+            return Err(format!(
+                "`water_duration` must be positive, got {}",
+                self.water_duration
+            ));
+        }
+        if self.crops.is_empty() {
+            return Err("there are no crops".to_string());
+        }
+        for (index, crop) in self.crops.iter().enumerate() {
+            if self.crops[..index].iter().any(|other| other.id == crop.id) {
+                return Err(format!("duplicate crop id `{}`", crop.id));
+            }
+            if crop.stages.len() < 2 {
+                return Err(format!("crop `{}` needs at least 2 stages", crop.id));
+            }
+            let growing = &crop.stages[..crop.stages.len() - 1];
+            if growing.iter().any(|stage| stage.duration <= 0.0) {
+                return Err(format!(
+                    "every stage but the last of crop `{}` must have a positive `duration`",
+                    crop.id
+                ));
+            }
+            let columns = crop.stages.iter().map(|stage| stage.column);
+            if columns
+                .chain([crop.withered_column])
+                .any(|column| column >= self.atlas_columns)
+            {
+                return Err(format!(
+                    "crop `{}` uses a column outside the atlas",
+                    crop.id
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 一株作物，是所在土壤实体的子实体。
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Crop {
+    /// 作物类型，对应 [`CropDef::id`]。
+    pub kind: String,
+    /// 当前生长阶段（[`CropDef::stages`] 中的索引）。
+    pub stage: usize,
+    /// 在当前阶段已经生长的时间（游戏分钟）。
+    pub growth: f32,
+    /// 土壤连续干燥的时间（游戏分钟）。
+    pub dry_time: f32,
+    pub withered: bool,
+}
+
+impl Crop {
+    /// 是否已经成熟，可以收获。
+    pub fn is_mature(&self, def: &CropDef) -> bool {
+        !self.withered && self.stage + 1 == def.stages.len()
+    }
+
+    /// 让作物经过 `delta` 游戏分钟：湿润的土壤中作物在生长季节内生长，干燥的土壤中作物逐渐枯萎。
+    pub fn grow(&mut self, def: &CropDef, watered: bool, season: Season, delta: f32) {
+        if self.withered {
+            return;
+        }
+        if !watered {
+            self.dry_time += delta;
+            if self.dry_time >= def.wither_after {
+                self.withered = true;
+            }
+            return;
+        }
+        self.dry_time = 0.0;
+        if self.is_mature(def) || !def.grows_in(season) {
+            return;
+        }
+        self.growth += delta;
+        if self.growth >= def.stages[self.stage].duration {
+            self.stage += 1;
+            self.growth = 0.0;
+        }
+    }
+}
+
+/// 一株刚种下的作物实体。
+pub fn crop(def: &CropDef, crop_assets: &CropAssets, atlas: &CropAtlas, size: f32) -> impl Bundle {
+    let crop = Crop {
+        kind: def.id.clone(),
+        stage: 0,
+        growth: 0.0,
+        dry_time: 0.0,
+        withered: false,
+    };
+    (
+        Name::new(format!("Crop ({})", def.id)),
+        crop_sprite(def, &crop, crop_assets, atlas, size),
+        crop,
+        Transform::from_xyz(0.0, 0.0, 1.0),
+        Pickable::IGNORE,
+        Saved,
+    )
+}
+
+/// 作物在当前生长阶段的精灵。
+pub fn crop_sprite(
+    def: &CropDef,
+    crop: &Crop,
+    crop_assets: &CropAssets,
+    atlas: &CropAtlas,
+    size: f32,
+) -> Sprite {
+    Sprite {
+        image: crop_assets.atlas.clone(),
+        texture_atlas: Some(TextureAtlas {
+            layout: atlas.layout.clone(),
+            index: def.atlas_index(crop, atlas.columns),
+        }),
+        custom_size: Some(Vec2::splat(size)),
+        ..default()
+    }
+}
+
+/// 收获了作物。
+#[derive(Message, Clone, Debug, PartialEq, Eq)]
+pub struct CropHarvested {
+    /// 获得的物品标识。
+    pub item: String,
+    /// 获得的物品数量。
+    pub count: u32,
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct CropAssets {
+    #[dependency]
+    atlas: Handle<Image>,
+}
+
+impl FromWorld for CropAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            atlas: assets.load_with_settings(
+                "images/crops.png",
+                |settings: &mut ImageLoaderSettings| {
+                    // 使用 `nearest` 采样以保持像素风格。
+                    settings.sampler = ImageSampler::nearest();
+                },
+            ),
+        }
+    }
+}
+
+/// 作物纹理图集的布局，根据作物文件生成。仅在游戏运行时存在。
+#[derive(Resource)]
+pub struct CropAtlas {
+    layout: Handle<TextureAtlasLayout>,
+    columns: u32,
+}
+
+fn init_crop_atlas(
+    mut commands: Commands,
+    crop_data: Res<CropData>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    let layout = TextureAtlasLayout::from_grid(
+        UVec2::splat(16),
+        crop_data.atlas_columns,
+        crop_data.atlas_rows(),
+        None,
+        None,
+    );
+    commands.insert_resource(CropAtlas {
+        layout: texture_atlas_layouts.add(layout),
+        columns: crop_data.atlas_columns,
+    });
+}
+
+fn remove_crop_atlas(mut commands: Commands) {
+    commands.remove_resource::<CropAtlas>();
+}
+
+/// 湿润土壤中的作物生长，干燥土壤中的作物逐渐枯萎。
+fn grow_crops(
+    clock: Res<GameClock>,
+    crop_data: Res<CropData>,
+    soil_query: Query<&Soil>,
+    mut crop_query: Query<(&mut Crop, &ChildOf)>,
+) {
+    for (mut crop, child_of) in &mut crop_query {
+        let (Some(def), Ok(soil)) = (crop_data.get(&crop.kind), soil_query.get(child_of.parent()))
+        else {
+            continue;
+        };
+        // 枯萎的作物不会再变化，不需要触发变化检测。
+        if !crop.withered {
+            crop.grow(
+                def,
+                soil.is_watered(),
+                clock.season(),
+                clock.delta_minutes(),
+            );
+        }
+    }
+}
+
+/// 进入新的季节时，不能在这个季节生长的作物枯萎。
+fn wither_out_of_season(
+    mut season_changed: MessageReader<SeasonChanged>,
+    crop_data: Res<CropData>,
+    mut crop_query: Query<&mut Crop>,
+) {
+    for SeasonChanged { season } in season_changed.read() {
+        for mut crop in &mut crop_query {
+            if !crop.withered
+                && crop_data
+                    .get(&crop.kind)
+                    .is_some_and(|def| !def.grows_in(*season))
+            {
+                crop.withered = true;
+            }
+        }
+    }
+}
+
+fn update_crop_sprites(
+    crop_data: Res<CropData>,
+    atlas: Res<CropAtlas>,
+    mut crop_query: Query<(&Crop, &mut Sprite), Changed<Crop>>,
+) {
+    for (crop, mut sprite) in &mut crop_query {
+        let (Some(def), Some(texture_atlas)) =
+            (crop_data.get(&crop.kind), sprite.texture_atlas.as_mut())
+        else {
+            continue;
+        };
+        let index = def.atlas_index(crop, atlas.columns);
+        if texture_atlas.index != index {
+            texture_atlas.index = index;
+        }
+    }
+}
+
+/// 记录收获的作物数量。收获的物品在使用镰刀时已经放进背包。
+fn record_harvests(mut harvested: MessageReader<CropHarvested>, mut stats: ResMut<RunStats>) {
+    for CropHarvested { item, count } in harvested.read() {
+        info!("Harvested {count} x {item}");
+        stats.add(FARMING_CROPS_HARVESTED, *count);
+    }
+}
+
+/// 作物文件引用了不存在的物品时发出警告。
+fn check_crop_references(crop_data: Res<CropData>, items: Res<ItemData>) {
+    for crop in &crop_data.crops {
+        for item in [&crop.seed, &crop.harvest.item] {
+            if items.get(item).is_none() {
+                warn!("Crop `{}` references unknown item `{item}`", crop.id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turnip() -> CropDef {
+        let stage = |column, duration| GrowthStage { column, duration };
+        CropDef {
+            id: "turnip".to_string(),
+            seed: "turnip_seeds".to_string(),
+            atlas_row: 0,
+            stages: vec![stage(0, 10.0), stage(1, 20.0), stage(2, 0.0)],
+            withered_column: 3,
+            wither_after: 30.0,
+            seasons: vec![Season::Spring, Season::Summer],
+            harvest: HarvestDef {
+                item: "turnip".to_string(),
+                count: 1,
+            },
+        }
+    }
+
+    fn planted() -> Crop {
+        Crop {
+            kind: "turnip".to_string(),
+            stage: 0,
+            growth: 0.0,
+            dry_time: 0.0,
+            withered: false,
+        }
+    }
+
+    #[test]
+    fn test_crop_advances_stages() {
+        let def = turnip();
+        let mut crop = planted();
+        crop.grow(&def, true, Season::Spring, 9.0);
+        assert_eq!((crop.stage, crop.growth), (0, 9.0));
+        crop.grow(&def, true, Season::Spring, 1.0);
+        assert_eq!((crop.stage, crop.growth), (1, 0.0));
+        crop.grow(&def, true, Season::Summer, 20.0);
+        assert_eq!(crop.stage, 2);
+        assert!(crop.is_mature(&def));
+        // 成熟后不再生长。
+        crop.grow(&def, true, Season::Summer, 100.0);
+        assert_eq!((crop.stage, crop.growth), (2, 0.0));
+    }
+
+    #[test]
+    fn test_crop_needs_water() {
+        let def = turnip();
+        let mut crop = planted();
+        crop.grow(&def, false, Season::Spring, 20.0);
+        assert_eq!((crop.stage, crop.growth, crop.dry_time), (0, 0.0, 20.0));
+        // 浇水后重新计算干燥时间。
+        crop.grow(&def, true, Season::Spring, 5.0);
+        assert_eq!((crop.growth, crop.dry_time), (5.0, 0.0));
+        crop.grow(&def, false, Season::Spring, 29.0);
+        assert!(!crop.withered);
+        crop.grow(&def, false, Season::Spring, 1.0);
+        assert!(crop.withered);
+        assert!(!crop.is_mature(&def));
+        // 枯萎的作物浇水后也不会恢复。
+        crop.grow(&def, true, Season::Spring, 100.0);
+        assert_eq!((crop.stage, crop.growth), (0, 5.0));
+        assert!(crop.withered);
+    }
+
+    #[test]
+    fn test_crop_season_gate() {
+        let def = turnip();
+        let mut crop = planted();
+        crop.grow(&def, true, Season::Winter, 100.0);
+        assert_eq!((crop.stage, crop.growth), (0, 0.0));
+        assert!(!crop.withered);
+        assert!(def.grows_in(Season::Summer));
+        assert!(!def.grows_in(Season::Autumn));
+        // 没有指定季节的作物在所有季节都可以生长。
+        let def = CropDef {
+            seasons: Vec::new(),
+            ..turnip()
+        };
+        crop.grow(&def, true, Season::Winter, 10.0);
+        assert_eq!(crop.stage, 1);
+    }
+}
@@ -0,0 +1,81 @@
+//! 在田地里劳作的农夫，也就是玩家角色。
+//!
+//! 农夫带着一些种子和金币开始游戏，按 B 打开商店买入种子或卖出收获的作物。
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    Pause,
+    games::{
+        farming::Farming,
+        gold::Gold,
+        inventory::{Inventory, ItemData},
+        mini_game::MiniGame,
+        player::{PlayerAssets, player},
+    },
+    menus::Menu,
+    save::Saved,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Farming::state()), spawn_farmer);
+    app.add_systems(OnExit(Farming::state()), remove_gold);
+    app.add_systems(
+        Update,
+        open_shop.run_if(
+            in_state(Farming::state())
+                .and(in_state(Menu::None))
+                .and(input_just_pressed(KeyCode::KeyB)),
+        ),
+    );
+}
+
+/// 农夫每秒的最大移动速度（以世界单位为单位）。
+const FARMER_SPEED: f32 = 240.0;
+/// 农夫精灵的缩放比例，使其与土壤格子大小相当。
+const FARMER_SCALE: f32 = 1.5;
+/// 背包的格子数。
+const INVENTORY_SIZE: usize = 16;
+/// 每局开始时的金币。
+const STARTING_GOLD: u32 = 50;
+/// 每局开始时背包中的物品。
+const STARTING_ITEMS: [(&str, u32); 3] = [
+    ("turnip_seeds", 10),
+    ("wheat_seeds", 5),
+    ("pumpkin_seeds", 2),
+];
+
+fn spawn_farmer(
+    mut commands: Commands,
+    player_assets: Res<PlayerAssets>,
+    items: Option<Res<ItemData>>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    let mut inventory = Inventory::new(INVENTORY_SIZE);
+    // 物品目录加载失败时背包为空。
+    if let Some(items) = items {
+        for (item, count) in STARTING_ITEMS {
+            inventory.add(&items, item, count);
+        }
+    }
+    commands
+        .spawn((
+            player(FARMER_SPEED, &player_assets, &mut texture_atlas_layouts),
+            inventory,
+            Saved,
+            DespawnOnExit(Farming::state()),
+        ))
+        // 覆盖默认的缩放，并显示在土壤和作物之上。
+        .insert(Transform::from_xyz(0.0, 0.0, 5.0).with_scale(Vec3::splat(FARMER_SCALE)));
+    commands.insert_resource(Gold(STARTING_GOLD));
+}
+
+fn remove_gold(mut commands: Commands) {
+    commands.remove_resource::<Gold>();
+}
+
+/// 暂停游戏并打开商店。
+fn open_shop(mut next_pause: ResMut<NextState<Pause>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_pause.set(Pause(true));
+    next_menu.set(Menu::Shop);
+}
@@ -0,0 +1,455 @@
+//! 农场的田地：一块由土壤格子组成的网格。
+//!
+//! 土壤需要先开垦才能播种。浇水后土壤会在一段时间内保持湿润，然后逐渐变干。
+//! 玩家使用工具作用于面前的土壤格子：锄头开垦，种子袋播种，水壶浇水，
+//! 镰刀收获成熟的作物或清除枯萎的作物。
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    games::{
+        clock::GameClock,
+        farming::{
+            Farming,
+            crop::{
+                Crop, CropAssets, CropAtlas, CropData, CropDef, CropHarvested, HarvestDef, crop,
+                crop_sprite,
+            },
+        },
+        grid::GridLayout,
+        inventory::{Inventory, ItemData},
+        mini_game::MiniGame,
+        player::{Player, Tool, ToolUsed},
+    },
+    save::{RegisterSaved, Saved},
+};
+
+/// 田地的大小（以格子为单位）。
+const FIELD_SIZE: UVec2 = UVec2::new(12, 8);
+/// 每个格子的边长（以世界单位为单位）。
+const TILE_SIZE: f32 = 48.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<FarmField>();
+    app.save_component::<Soil>();
+
+    app.add_observer(restore_soil_sprite);
+    app.add_observer(restore_crop_sprite);
+
+    app.add_systems(OnEnter(Farming::state()), spawn_field);
+    app.add_systems(OnExit(Farming::state()), remove_field);
+
+    app.add_systems(
+        Update,
+        (
+            dry_soil,
+            use_tools.run_if(
+                on_message::<ToolUsed>
+                    .and(resource_exists::<CropAtlas>)
+                    .and(resource_exists::<ItemData>),
+            ),
+            update_soil_sprites,
+        )
+            .chain()
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Farming::state()))
+            .in_set(PausableSystems),
+    );
+}
+
+/// 田地的网格布局。仅在游戏运行时存在。
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct FarmField {
+    pub layout: GridLayout,
+}
+
+/// 一个土壤格子。
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Soil {
+    pub pos: IVec2,
+    /// 是否已经开垦。
+    pub tilled: bool,
+    /// 剩余的湿润时间（游戏分钟）。
+    pub moisture: f32,
+}
+
+impl Soil {
+    /// 土壤是否湿润。
+    pub fn is_watered(&self) -> bool {
+        self.moisture > 0.0
+    }
+
+    fn color(&self) -> Color {
+        match (self.tilled, self.is_watered()) {
+            (false, _) => Color::srgb(0.376, 0.627, 0.251),
+            (true, false) => Color::srgb(0.600, 0.420, 0.250),
+            (true, true) => Color::srgb(0.380, 0.250, 0.150),
+        }
+    }
+}
+
+/// 土壤格子的精灵。
+fn soil_sprite(soil: &Soil) -> Sprite {
+    Sprite::from_color(soil.color(), Vec2::splat(TILE_SIZE - 2.0))
+}
+
+fn spawn_field(mut commands: Commands) {
+    let layout = GridLayout::new(FIELD_SIZE, TILE_SIZE);
+    // 土壤格子是顶层实体，这样保存它们时不需要一起保存田地本身。
+    commands.spawn_batch(layout.positions().map(move |pos| {
+        let soil = Soil {
+            pos,
+            tilled: false,
+            moisture: 0.0,
+        };
+        (
+            Name::new(format!("Soil {pos}")),
+            soil_sprite(&soil),
+            Transform::from_translation(layout.grid_to_world(pos).extend(-10.0)),
+            soil,
+            Saved,
+            DespawnOnExit(Farming::state()),
+        )
+    }));
+    commands.insert_resource(FarmField { layout });
+}
+
+/// 为从存档中读取的土壤补全精灵。
+fn restore_soil_sprite(
+    add: On<Add, Soil>,
+    mut commands: Commands,
+    soil_query: Query<&Soil, Without<Sprite>>,
+) {
+    if let Ok(soil) = soil_query.get(add.entity) {
+        commands.entity(add.entity).insert(soil_sprite(soil));
+    }
+}
+
+/// 为从存档中读取的作物补全精灵。
+fn restore_crop_sprite(
+    add: On<Add, Crop>,
+    mut commands: Commands,
+    crop_query: Query<&Crop, Without<Sprite>>,
+    crop_data: Option<Res<CropData>>,
+    crop_assets: Res<CropAssets>,
+    atlas: Option<Res<CropAtlas>>,
+) {
+    let (Ok(planted), Some(crop_data), Some(atlas)) =
+        (crop_query.get(add.entity), crop_data, atlas)
+    else {
+        return;
+    };
+    let Some(def) = crop_data.get(&planted.kind) else {
+        warn!("Saved crop has unknown kind `{}`", planted.kind);
+        return;
+    };
+    commands.entity(add.entity).insert((
+        crop_sprite(def, planted, &crop_assets, &atlas, TILE_SIZE),
+        Pickable::IGNORE,
+    ));
+}
+
+fn remove_field(mut commands: Commands) {
+    commands.remove_resource::<FarmField>();
+}
+
+fn dry_soil(clock: Res<GameClock>, mut soil_query: Query<&mut Soil>) {
+    for mut soil in &mut soil_query {
+        if soil.is_watered() {
+            soil.moisture = (soil.moisture - clock.delta_minutes()).max(0.0);
+        }
+    }
+}
+
+fn update_soil_sprites(mut soil_query: Query<(&Soil, &mut Sprite), Changed<Soil>>) {
+    for (soil, mut sprite) in &mut soil_query {
+        let color = soil.color();
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
+
+/// 对土壤格子使用工具的结果。生成或移除作物实体由调用者完成。
+#[derive(Debug)]
+enum ToolOutcome<'a> {
+    /// 工具对这个格子没有作用。
+    Nothing,
+    /// 开垦或浇水，只改变了土壤。
+    SoilChanged,
+    /// 从背包中取出一个种子，种下这种作物。
+    Planted(&'a CropDef),
+    /// 收获了成熟的作物，物品已经放进背包。
+    Harvested(CropHarvested),
+    /// 清除了枯萎的作物。
+    Cleared,
+}
+
+/// 对土壤格子使用工具，`planted` 是格子中已经种下的作物。
+fn apply_tool<'a>(
+    tool: Tool,
+    soil: &mut Soil,
+    planted: Option<&Crop>,
+    crop_data: &'a CropData,
+    items: &ItemData,
+    inventory: &mut Inventory,
+) -> ToolOutcome<'a> {
+    match (tool, planted) {
+        (Tool::Hoe, None) if !soil.tilled => {
+            soil.tilled = true;
+            ToolOutcome::SoilChanged
+        }
+        (Tool::WateringCan, _) if soil.tilled => {
+            soil.moisture = crop_data.water_duration;
+            ToolOutcome::SoilChanged
+        }
+        (Tool::SeedBag, None) if soil.tilled => {
+            // 播种快捷栏中选中的种子。
+            let Some(def) = inventory
+                .selected_stack()
+                .and_then(|stack| crop_data.by_seed(&stack.item))
+            else {
+                return ToolOutcome::Nothing;
+            };
+            let selected = inventory.selected;
+            if inventory.take(selected, 1).is_err() {
+                return ToolOutcome::Nothing;
+            }
+            ToolOutcome::Planted(def)
+        }
+        (Tool::Sickle, Some(planted)) => {
+            let Some(def) = crop_data.get(&planted.kind) else {
+                return ToolOutcome::Nothing;
+            };
+            if planted.withered {
+                return ToolOutcome::Cleared;
+            }
+            if !planted.is_mature(def) {
+                return ToolOutcome::Nothing;
+            }
+            let HarvestDef { item, count } = &def.harvest;
+            let overflow = inventory.add(items, item, *count);
+            if overflow > 0 {
+                warn!("Inventory is full, lost {overflow} x {item}");
+            }
+            ToolOutcome::Harvested(CropHarvested {
+                item: item.clone(),
+                count: *count,
+            })
+        }
+        _ => ToolOutcome::Nothing,
+    }
+}
+
+/// 对玩家面前的土壤格子使用工具。
+fn use_tools(
+    mut commands: Commands,
+    mut tool_used: MessageReader<ToolUsed>,
+    field: Res<FarmField>,
+    mut soil_query: Query<(Entity, &mut Soil, Option<&Children>)>,
+    crop_query: Query<&Crop>,
+    crop_data: Res<CropData>,
+    crop_assets: Res<CropAssets>,
+    atlas: Res<CropAtlas>,
+    items: Res<ItemData>,
+    mut inventory: Single<&mut Inventory, With<Player>>,
+    mut harvested: MessageWriter<CropHarvested>,
+) {
+    for ToolUsed { tool, target } in tool_used.read() {
+        let Some(pos) = field.layout.world_to_grid(*target) else {
+            continue;
+        };
+        let Some((soil_entity, mut soil, children)) =
+            soil_query.iter_mut().find(|(_, soil, _)| soil.pos == pos)
+        else {
+            continue;
+        };
+        let planted = children
+            .into_iter()
+            .flatten()
+            .find_map(|entity| Some((*entity, crop_query.get(*entity).ok()?)));
+
+        match apply_tool(
+            *tool,
+            &mut soil,
+            planted.map(|(_, planted)| planted),
+            &crop_data,
+            &items,
+            &mut inventory,
+        ) {
+            ToolOutcome::Nothing | ToolOutcome::SoilChanged => {}
+            ToolOutcome::Planted(def) => {
+                commands
+                    .entity(soil_entity)
+                    .with_child(crop(def, &crop_assets, &atlas, TILE_SIZE));
+            }
+            ToolOutcome::Harvested(crop) => {
+                harvested.write(crop);
+                if let Some((crop_entity, _)) = planted {
+                    commands.entity(crop_entity).despawn();
+                }
+            }
+            ToolOutcome::Cleared => {
+                if let Some((crop_entity, _)) = planted {
+                    commands.entity(crop_entity).despawn();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::{farming::crop::GrowthStage, inventory::ItemDef};
+
+    fn crop_data() -> CropData {
+        let stage = |column| GrowthStage {
+            column,
+            duration: 10.0,
+        };
+        CropData {
+            atlas_columns: 3,
+            water_duration: 100.0,
+            crops: vec![CropDef {
+                id: "turnip".to_string(),
+                seed: "turnip_seeds".to_string(),
+                atlas_row: 0,
+                stages: vec![stage(0), stage(1)],
+                withered_column: 2,
+                wither_after: 50.0,
+                seasons: Vec::new(),
+                harvest: HarvestDef {
+                    item: "turnip".to_string(),
+                    count: 2,
+                },
+            }],
+        }
+    }
+
+    fn items() -> ItemData {
+        let item = |id: &str| ItemDef {
+            id: id.to_string(),
+            name: String::new(),
+            icon: String::new(),
+            max_stack: 10,
+        };
+        ItemData {
+            items: vec![item("turnip_seeds"), item("turnip")],
+        }
+    }
+
+    fn soil() -> Soil {
+        Soil {
+            pos: IVec2::ZERO,
+            tilled: false,
+            moisture: 0.0,
+        }
+    }
+
+    fn turnip(stage: usize, withered: bool) -> Crop {
+        Crop {
+            kind: "turnip".to_string(),
+            stage,
+            growth: 0.0,
+            dry_time: 0.0,
+            withered,
+        }
+    }
+
+    #[test]
+    fn test_hoe_tills_soil() {
+        let (crop_data, items) = (crop_data(), items());
+        let mut inventory = Inventory::new(2);
+        let mut soil = soil();
+        let mut apply =
+            |soil: &mut Soil| apply_tool(Tool::Hoe, soil, None, &crop_data, &items, &mut inventory);
+        assert!(matches!(apply(&mut soil), ToolOutcome::SoilChanged));
+        assert!(soil.tilled);
+        assert!(matches!(apply(&mut soil), ToolOutcome::Nothing));
+    }
+
+    #[test]
+    fn test_watering_can_waters_tilled_soil() {
+        let (crop_data, items) = (crop_data(), items());
+        let mut inventory = Inventory::new(2);
+        let mut soil = soil();
+        let mut apply = |soil: &mut Soil| {
+            apply_tool(
+                Tool::WateringCan,
+                soil,
+                None,
+                &crop_data,
+                &items,
+                &mut inventory,
+            )
+        };
+        // 未开垦的土壤不能浇水。
+        assert!(matches!(apply(&mut soil), ToolOutcome::Nothing));
+        assert!(!soil.is_watered());
+        soil.tilled = true;
+        assert!(matches!(apply(&mut soil), ToolOutcome::SoilChanged));
+        assert_eq!(soil.moisture, 100.0);
+    }
+
+    #[test]
+    fn test_seed_bag_plants_selected_seed() {
+        let (crop_data, items) = (crop_data(), items());
+        let mut inventory = Inventory::new(2);
+        inventory.add(&items, "turnip_seeds", 1);
+        let mut soil = Soil {
+            tilled: true,
+            ..soil()
+        };
+        let mut apply = |inventory: &mut Inventory| {
+            apply_tool(
+                Tool::SeedBag,
+                &mut soil,
+                None,
+                &crop_data,
+                &items,
+                inventory,
+            )
+        };
+        assert!(matches!(apply(&mut inventory), ToolOutcome::Planted(def) if def.id == "turnip"));
+        assert_eq!(inventory.count("turnip_seeds"), 0);
+        // 没有种子时不能播种。
+        assert!(matches!(apply(&mut inventory), ToolOutcome::Nothing));
+    }
+
+    #[test]
+    fn test_sickle_harvests_into_inventory() {
+        let (crop_data, items) = (crop_data(), items());
+        let mut inventory = Inventory::new(2);
+        let mut soil = soil();
+        let mut apply = |planted: &Crop, inventory: &mut Inventory| {
+            apply_tool(
+                Tool::Sickle,
+                &mut soil,
+                Some(planted),
+                &crop_data,
+                &items,
+                inventory,
+            )
+        };
+        // 未成熟的作物不能收获。
+        assert!(matches!(
+            apply(&turnip(0, false), &mut inventory),
+            ToolOutcome::Nothing
+        ));
+        assert!(matches!(
+            apply(&turnip(1, false), &mut inventory),
+            ToolOutcome::Harvested(CropHarvested { item, count: 2 }) if item == "turnip"
+        ));
+        assert_eq!(inventory.count("turnip"), 2);
+        // 枯萎的作物只会被清除。
+        assert!(matches!(
+            apply(&turnip(1, true), &mut inventory),
+            ToolOutcome::Cleared
+        ));
+        assert_eq!(inventory.count("turnip"), 2);
+    }
+}
@@ -0,0 +1,106 @@
+//! 游戏中的 HUD：日期和时间、金币、当前选择的工具和快捷栏。
+
+use bevy::prelude::*;
+
+use crate::{
+    FntAssets,
+    games::{
+        clock::GameClock, farming::Farming, gold::Gold, inventory::hotbar, mini_game::MiniGame,
+        player::ToolBelt,
+    },
+    i18n::{
+        LocalizedText, TextArg,
+        config::{CLOCK_DAY, FARMING_TOOL, GOLD},
+    },
+    screens::Screen,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<HudLabel>();
+
+    app.add_systems(OnEnter(Farming::state()), spawn_hud);
+    app.add_systems(
+        Update,
+        (
+            update_clock_label.run_if(resource_changed::<GameClock>),
+            update_gold_label.run_if(resource_exists_and_changed::<Gold>),
+            update_tool_label,
+        )
+            .run_if(in_state(Farming::state())),
+    );
+}
+
+/// HUD 中的一个标签。
+#[derive(Component, Reflect, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+enum HudLabel {
+    Clock,
+    Gold,
+    Tool,
+}
+
+fn spawn_hud(mut commands: Commands, font_res: Res<FntAssets>) {
+    let font = font_res.pixel();
+    commands.spawn((
+        widget::hud_root("HUD"),
+        DespawnOnExit(Screen::Gameplay),
+        children![
+            (
+                widget::hud_bar("Status Bar"),
+                children![
+                    widget::hud_label(HudLabel::Clock, LocalizedText::new(CLOCK_DAY), font.clone()),
+                    widget::hud_label(HudLabel::Gold, LocalizedText::new(GOLD), font.clone()),
+                    widget::hud_label(
+                        HudLabel::Tool,
+                        LocalizedText::new(FARMING_TOOL),
+                        font.clone()
+                    ),
+                ],
+            ),
+            hotbar(font),
+        ],
+    ));
+}
+
+fn update_clock_label(
+    clock: Res<GameClock>,
+    mut label_query: Query<(&HudLabel, &mut LocalizedText)>,
+) {
+    let text = LocalizedText::new(CLOCK_DAY)
+        .with_arg("day", clock.day_of_season())
+        .with_arg("season", TextArg::Key(clock.season().name().into()))
+        .with_arg("time", format!("{:02}:{:02}", clock.hour(), clock.minute()));
+    for (_, mut label) in label_query
+        .iter_mut()
+        .filter(|(l, _)| **l == HudLabel::Clock)
+    {
+        label.set_if_neq(text.clone());
+    }
+}
+
+fn update_gold_label(gold: Res<Gold>, mut label_query: Query<(&HudLabel, &mut LocalizedText)>) {
+    let text = LocalizedText::new(GOLD).with_arg("gold", gold.0);
+    for (_, mut label) in label_query
+        .iter_mut()
+        .filter(|(l, _)| **l == HudLabel::Gold)
+    {
+        label.set_if_neq(text.clone());
+    }
+}
+
+fn update_tool_label(
+    tool_query: Query<&ToolBelt, Changed<ToolBelt>>,
+    mut label_query: Query<(&HudLabel, &mut LocalizedText)>,
+) {
+    for tool_belt in &tool_query {
+        let text = LocalizedText::new(FARMING_TOOL)
+            .with_arg("tool", TextArg::Key(tool_belt.selected.name().into()));
+        for (_, mut label) in label_query
+            .iter_mut()
+            .filter(|(l, _)| **l == HudLabel::Tool)
+        {
+            label.set_if_neq(text.clone());
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! “种个锤子地”：在田地里开垦、播种、浇水和收获。
+
+use bevy::prelude::*;
+
+use crate::{audio::AddStateMusic, games::mini_game::MiniGame};
+
+pub mod crop;
+mod farmer;
+pub mod field;
+mod hud;
+mod stats;
+
+pub struct Farming;
+
+impl MiniGame for Farming {
+    const ID: &'static str = "farming";
+    const SAVEABLE: bool = true;
+
+    fn plugin(app: &mut App) {
+        app.add_state_music(
+            Self::state(),
+            &[
+                "audio/music/Monkeys Spinning Monkeys.ogg",
+                "audio/music/Fluffing A Duck.ogg",
+            ],
+        );
+        app.add_plugins((
+            crop::plugin,
+            farmer::plugin,
+            field::plugin,
+            hud::plugin,
+            stats::plugin,
+        ));
+    }
+}
@@ -0,0 +1,37 @@
+//! 在 [`RunStats`] 中记录本局的统计数据。
+
+use bevy::prelude::*;
+
+use crate::{
+    games::{
+        clock::{DayStarted, GameClock},
+        farming::Farming,
+        mini_game::{MiniGame, RunStats},
+    },
+    i18n::config::{FARMING_CROPS_HARVESTED, FARMING_DAYS},
+    save::GameLoaded,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Farming::state()), init_stats);
+    app.add_systems(
+        Update,
+        (
+            // 读取存档后，天数来自存档中的时钟。
+            init_stats.run_if(on_message::<GameLoaded>),
+            count_days.run_if(on_message::<DayStarted>),
+        )
+            .run_if(in_state(Farming::state())),
+    );
+}
+
+fn init_stats(clock: Res<GameClock>, mut stats: ResMut<RunStats>) {
+    stats.set(FARMING_DAYS, clock.day());
+    stats.set(FARMING_CROPS_HARVESTED, 0);
+}
+
+fn count_days(mut day_started: MessageReader<DayStarted>, mut stats: ResMut<RunStats>) {
+    for started in day_started.read() {
+        stats.set(FARMING_DAYS, started.day);
+    }
+}
@@ -1,37 +1,78 @@
 //! 游戏列表
+//!
+//! 游戏目录从 `assets/games.ron` 加载，新增小游戏时只需要修改该文件。
 
-use std::str;
-
-use bevy::prelude::*;
+use bevy::{platform::collections::HashSet, prelude::*};
+use serde::Deserialize;
 
 use crate::{
-    asset_tracking::ResourceHandles,
-    i18n::config::{GAMES_DESCRIPTION_1, GAMES_NAME_1},
+    asset_tracking::{LoadResource, ResourceHandles, RonAsset, RonAssetLoader},
+    games::mini_game::{MiniGameRegistry, SelectedGame},
+    i18n::LanguageRes,
     screens::Screen,
 };
 
+/// 游戏目录文件的路径。
+const GAME_LIST_PATH: &str = "games.ron";
+
 pub(super) fn plugin(app: &mut App) {
     app.add_message::<OpenGameEvent>();
     app.register_type::<GameList>();
-    app.insert_resource(load_game_list());
+    app.register_asset_loader(RonAssetLoader::<GameList>::default());
+    app.load_resource_from_path::<GameList>(GAME_LIST_PATH);
     app.add_observer(open_game_event_handler);
+    app.add_systems(
+        Update,
+        check_game_list_references.run_if(resource_exists_and_changed::<GameList>),
+    );
 }
 
 /// 游戏列表资源
-#[derive(Resource, Asset, Clone, Reflect)]
+#[derive(Resource, Asset, Clone, Reflect, Deserialize)]
 #[reflect(Resource)]
 pub struct GameList {
     pub games: Vec<GameItem>,
 }
 
-#[derive(Clone, Reflect)]
+#[derive(Clone, Reflect, Deserialize)]
 pub struct GameItem {
-    /// 游戏名称
+    /// 游戏的唯一标识
+    pub id: String,
+    /// 游戏名称的多语言 Key
     pub name: String,
-    /// 游戏描述
+    /// 游戏描述的多语言 Key
     pub description: String,
-    /// 游戏图标路径
+    /// 游戏图标路径（相对于 `assets` 目录）
     pub icon: String,
+    /// 实现该游戏的插件标识
+    pub plugin: String,
+}
+
+impl RonAsset for GameList {
+    const EXTENSIONS: &'static [&'static str] = &["games.ron"];
+
+    fn validate(&self) -> Result<(), String> {
+        let mut ids = HashSet::new();
+        for (index, game) in self.games.iter().enumerate() {
+            if game.id.is_empty() {
+                return Err(format!("game #{index} has an empty `id`"));
+            }
+            if !ids.insert(game.id.as_str()) {
+                return Err(format!("duplicate game id `{}`", game.id));
+            }
+            for (field, value) in [
+                ("name", &game.name),
+                ("description", &game.description),
+                ("icon", &game.icon),
+                ("plugin", &game.plugin),
+            ] {
+                if value.is_empty() {
+                    return Err(format!("game `{}` has an empty `{field}`", game.id));
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 ///打开游戏事件
@@ -40,26 +81,49 @@ pub struct OpenGameEvent {
     pub index: u8,
 }
 
-fn load_game_list() -> GameList {
-    // 这里可以从配置文件或其他资源加载游戏列表
-    // 例如，假设我们有一个 JSON 文件包含游戏列表
-    let games = vec![GameItem {
-        name: GAMES_NAME_1.to_string(),
-        description: GAMES_DESCRIPTION_1.to_string(),
-        icon: "assets/icons/game1.png".to_string(),
-    }];
-    GameList { games }
+/// 游戏目录引用了不存在的多语言 Key 或未注册的插件时发出警告。
+fn check_game_list_references(
+    game_list: Res<GameList>,
+    lang_res: Res<LanguageRes>,
+    registry: Res<MiniGameRegistry>,
+) {
+    for game in &game_list.games {
+        if registry.get(&game.plugin).is_none() {
+            warn!(
+                "Game `{}` references unregistered plugin `{}`",
+                game.id, game.plugin
+            );
+        }
+        for key in [&game.name, &game.description] {
+            if !lang_res.contains(key) {
+                warn!("Game `{}` references missing text key `{key}`", game.id);
+            }
+        }
+    }
 }
 
 fn open_game_event_handler(
     open_game_triggers: On<OpenGameEvent>,
-    game_list: Res<GameList>,
+    game_list: Option<Res<GameList>>,
+    registry: Res<MiniGameRegistry>,
     resource_handles: Res<ResourceHandles>,
+    mut selected_game: ResMut<SelectedGame>,
     mut next_screen: ResMut<NextState<Screen>>,
 ) {
     let event = open_game_triggers.event();
+    let Some(game_list) = game_list else {
+        return;
+    };
     if let Some(game) = game_list.games.get(event.index as usize) {
-        info!("Opening game: {}", game.name);
+        let Some(plugin) = registry.get(&game.plugin) else {
+            error!(
+                "Cannot open game `{}`: plugin `{}` is not registered",
+                game.id, game.plugin
+            );
+            return;
+        };
+        info!("Opening game: {}", game.id);
+        selected_game.0 = Some(plugin);
         if resource_handles.is_all_done() {
             next_screen.set(Screen::Gameplay);
         } else {
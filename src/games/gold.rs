@@ -0,0 +1,29 @@
+//! 金币。
+//!
+//! 每个需要金币的小游戏在开始时插入 [`Gold`]，并在退出时移除。
+
+use bevy::prelude::*;
+
+use crate::save::RegisterSaved;
+
+pub(super) fn plugin(app: &mut App) {
+    app.save_resource::<Gold>();
+}
+
+/// 当前拥有的金币。仅在使用金币的游戏运行时存在。
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+pub struct Gold(pub u32);
+
+impl Gold {
+    /// 金币足够时花费 `amount` 并返回 `true`，否则不花费并返回 `false`。
+    pub fn try_spend(&mut self, amount: u32) -> bool {
+        match self.0.checked_sub(amount) {
+            Some(rest) => {
+                self.0 = rest;
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! 网格工具：在世界坐标与网格坐标之间转换。
+//!
+//! 网格以世界原点为中心，网格坐标 `(0, 0)` 位于左下角，`x` 向右增长，`y` 向上增长。
+
+use bevy::prelude::*;
+
+/// 网格的尺寸和每个格子的大小。
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct GridLayout {
+    /// 网格的列数和行数。
+    pub size: UVec2,
+    /// 每个格子的边长（以世界单位为单位）。
+    pub tile_size: f32,
+}
+
+impl GridLayout {
+    pub fn new(size: UVec2, tile_size: f32) -> Self {
+        Self { size, tile_size }
+    }
+
+    /// 网格左下角在世界中的位置。
+    fn origin(&self) -> Vec2 {
+        -self.size.as_vec2() * self.tile_size / 2.0
+    }
+
+    /// 网格坐标是否在网格范围内。
+    pub fn contains(&self, pos: IVec2) -> bool {
+        pos.cmpge(IVec2::ZERO).all() && pos.cmplt(self.size.as_ivec2()).all()
+    }
+
+    /// 返回格子中心在世界中的位置。
+    pub fn grid_to_world(&self, pos: IVec2) -> Vec2 {
+        self.origin() + (pos.as_vec2() + 0.5) * self.tile_size
+    }
+
+    /// 返回世界位置所在的格子，如果在网格之外则返回 `None`。
+    pub fn world_to_grid(&self, world: Vec2) -> Option<IVec2> {
+        let pos = ((world - self.origin()) / self.tile_size)
+            .floor()
+            .as_ivec2();
+        self.contains(pos).then_some(pos)
+    }
+
+    /// 返回格子在按行存储的数组中的索引。
+    pub fn index(&self, pos: IVec2) -> Option<usize> {
+        self.contains(pos)
+            .then(|| (pos.y as u32 * self.size.x + pos.x as u32) as usize)
+    }
+
+    /// 返回按行存储的数组中索引对应的格子。
+    pub fn position(&self, index: usize) -> IVec2 {
+        let index = index as u32;
+        UVec2::new(index % self.size.x, index / self.size.x).as_ivec2()
+    }
+
+    /// 返回网格范围内上下左右相邻的格子。
+    pub fn neighbors(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + use<> {
+        let layout = *self;
+        [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+            .into_iter()
+            .map(move |offset| pos + offset)
+            .filter(move |neighbor| layout.contains(*neighbor))
+    }
+
+    /// 按行遍历网格中的所有格子。
+    pub fn positions(&self) -> impl Iterator<Item = IVec2> + use<> {
+        let layout = *self;
+        (0..(self.size.x * self.size.y) as usize).map(move |index| layout.position(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_world_round_trip() {
+        let layout = GridLayout::new(UVec2::new(4, 2), 10.0);
+        assert_eq!(layout.grid_to_world(IVec2::ZERO), Vec2::new(-15.0, -5.0));
+        for pos in layout.positions() {
+            assert_eq!(layout.world_to_grid(layout.grid_to_world(pos)), Some(pos));
+        }
+        assert_eq!(layout.world_to_grid(Vec2::new(20.1, 0.0)), None);
+        assert_eq!(layout.world_to_grid(Vec2::new(0.0, -10.1)), None);
+    }
+
+    #[test]
+    fn test_grid_index() {
+        let layout = GridLayout::new(UVec2::new(4, 2), 10.0);
+        assert_eq!(layout.index(IVec2::new(3, 1)), Some(7));
+        assert_eq!(layout.position(7), IVec2::new(3, 1));
+        assert_eq!(layout.index(IVec2::new(4, 0)), None);
+        assert_eq!(layout.neighbors(IVec2::ZERO).count(), 2);
+    }
+}
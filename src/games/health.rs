@@ -0,0 +1,201 @@
+//! 生命值、伤害和死亡。
+//!
+//! 任何带有 [`Health`] 的实体都可以通过 [`Damage`] 消息受到伤害。伤害依次经过
+//! [`Resistances`]（按伤害类型减免一定比例）和 [`Armor`]（减免固定数值）的计算，
+//! 处于无敌时间（[`Invulnerability`]）内的实体不会受到伤害。
+//!
+//! 生命值耗尽时会对该实体触发 [`Died`] 事件，游戏可以通过观察者在此时播放特效或结束游戏。
+//! 带有 [`DespawnOnDeath`] 的实体会在所有观察者运行之后被移除。
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use serde::Deserialize;
+
+use crate::{AppSystems, PausableSystems};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Health>();
+    app.register_type::<Armor>();
+    app.register_type::<Resistances>();
+    app.register_type::<Invulnerability>();
+    app.register_type::<DespawnOnDeath>();
+    app.add_message::<Damage>();
+
+    app.add_systems(
+        Update,
+        (
+            tick_invulnerability.in_set(AppSystems::TickTimers),
+            apply_damage.in_set(AppSystems::Update),
+        )
+            .in_set(PausableSystems),
+    );
+}
+
+/// 实体的生命值。
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+
+    /// 生命值是否已经耗尽。
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+}
+
+/// 伤害的类型。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect, Deserialize)]
+pub enum DamageKind {
+    #[default]
+    Physical,
+    Fire,
+    Frost,
+}
+
+/// 护甲：每次受到的伤害减少固定数值，但不会减少到 0 以下。
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component)]
+pub struct Armor(pub f32);
+
+/// 抗性：每种伤害类型减免的比例，`0.5` 表示受到一半伤害，负数表示受到额外伤害。
+#[derive(Component, Reflect, Clone, Debug, Default, Deserialize)]
+#[reflect(Component)]
+pub struct Resistances(pub HashMap<DamageKind, f32>);
+
+impl Resistances {
+    /// 返回该类型的伤害经过减免后的倍率。
+    pub fn multiplier(&self, kind: DamageKind) -> f32 {
+        1.0 - self.0.get(&kind).copied().unwrap_or(0.0).min(1.0)
+    }
+}
+
+/// 无敌时间：受到伤害后的一段时间内不会再次受到伤害。
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct Invulnerability {
+    timer: Timer,
+}
+
+impl Invulnerability {
+    /// 每次受到伤害后无敌 `seconds` 秒。
+    pub fn new(seconds: f32) -> Self {
+        let mut timer = Timer::from_seconds(seconds, TimerMode::Once);
+        timer.finish();
+        Self { timer }
+    }
+
+    /// 当前是否处于无敌时间内。
+    pub fn is_active(&self) -> bool {
+        !self.timer.is_finished()
+    }
+}
+
+/// 生命值耗尽时移除该实体。
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component)]
+pub struct DespawnOnDeath;
+
+/// 对一个实体造成伤害。
+#[derive(Message, Clone, Copy, Debug)]
+pub struct Damage {
+    pub target: Entity,
+    /// 减免前的伤害数值。
+    pub amount: f32,
+    pub kind: DamageKind,
+}
+
+/// 实体的生命值耗尽。
+#[derive(EntityEvent, Clone, Copy, Debug)]
+pub struct Died {
+    pub entity: Entity,
+}
+
+/// 计算减免后实际受到的伤害。
+pub fn damage_taken(
+    amount: f32,
+    kind: DamageKind,
+    armor: Option<&Armor>,
+    resistances: Option<&Resistances>,
+) -> f32 {
+    let amount = amount * resistances.map_or(1.0, |resistances| resistances.multiplier(kind));
+    (amount - armor.map_or(0.0, |armor| armor.0)).max(0.0)
+}
+
+fn tick_invulnerability(time: Res<Time>, mut query: Query<&mut Invulnerability>) {
+    for mut invulnerability in &mut query {
+        invulnerability.timer.tick(time.delta());
+    }
+}
+
+fn apply_damage(
+    mut commands: Commands,
+    mut damage_reader: MessageReader<Damage>,
+    mut health_query: Query<(
+        &mut Health,
+        Option<&Armor>,
+        Option<&Resistances>,
+        Option<&mut Invulnerability>,
+        Has<DespawnOnDeath>,
+    )>,
+) {
+    for damage in damage_reader.read() {
+        let Ok((mut health, armor, resistances, invulnerability, despawn)) =
+            health_query.get_mut(damage.target)
+        else {
+            continue;
+        };
+        if health.is_dead() {
+            continue;
+        }
+        if let Some(mut invulnerability) = invulnerability {
+            if invulnerability.is_active() {
+                continue;
+            }
+            invulnerability.timer.reset();
+        }
+
+        health.current -= damage_taken(damage.amount, damage.kind, armor, resistances);
+        if !health.is_dead() {
+            continue;
+        }
+        commands.trigger(Died {
+            entity: damage.target,
+        });
+        if despawn {
+            commands.entity(damage.target).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damage_taken() {
+        let resistances = Resistances(HashMap::from_iter([
+            (DamageKind::Fire, 0.5),
+            (DamageKind::Frost, -1.0),
+        ]));
+        let armor = Armor(2.0);
+        assert_eq!(damage_taken(10.0, DamageKind::Physical, None, None), 10.0);
+        assert_eq!(
+            damage_taken(10.0, DamageKind::Fire, Some(&armor), Some(&resistances)),
+            3.0
+        );
+        assert_eq!(
+            damage_taken(10.0, DamageKind::Frost, None, Some(&resistances)),
+            20.0
+        );
+        assert_eq!(
+            damage_taken(1.0, DamageKind::Physical, Some(&armor), None),
+            0.0
+        );
+    }
+}
@@ -0,0 +1,587 @@
+//! 背包和物品堆叠。
+//!
+//! 物品目录从 `assets/items.ron` 加载。[`Inventory`] 由固定数量的格子组成，每个格子放一堆相同的物品，
+//! 数量不超过该物品的 [`ItemDef::max_stack`]。放不下的物品不会被丢弃，而是作为溢出数量返回给调用者。
+//!
+//! 玩家背包的前几个格子显示在快捷栏（[`hotbar`]）中：左键点击选择格子，按 Q 选择下一个格子；
+//! 右键点击其他格子把选中的物品移动过去（物品相同时合并，不同时交换），右键点击选中的格子把它拆成两堆。
+
+use bevy::{
+    ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed,
+    platform::collections::HashSet, prelude::*, ui::Val::*,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    AppSystems, PausableSystems,
+    asset_tracking::{LoadResource, RonAsset, RonAssetLoader},
+    games::player::Player,
+    i18n::LanguageRes,
+    save::RegisterSaved,
+    theme::prelude::*,
+};
+
+/// 物品目录文件的路径。
+const ITEM_LIST_PATH: &str = "items.ron";
+/// 快捷栏显示的格子数。
+pub const HOTBAR_SLOTS: usize = 8;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ItemData>();
+    app.save_component::<Inventory>();
+    app.register_type::<HotbarSlot>();
+    app.register_asset_loader(RonAssetLoader::<ItemData>::default());
+    app.load_resource_from_path::<ItemData>(ITEM_LIST_PATH);
+
+    app.add_systems(
+        Update,
+        (
+            check_item_references.run_if(resource_exists_and_changed::<ItemData>),
+            cycle_hotbar_selection
+                .run_if(input_just_pressed(KeyCode::KeyQ))
+                .in_set(AppSystems::RecordInput)
+                .in_set(PausableSystems),
+            update_hotbar
+                .run_if(resource_exists::<ItemData>)
+                .in_set(AppSystems::Update),
+        ),
+    );
+}
+
+/// 物品目录。
+#[derive(Resource, Asset, Clone, Reflect, Deserialize)]
+#[reflect(Resource)]
+pub struct ItemData {
+    pub items: Vec<ItemDef>,
+}
+
+/// 一种物品的属性。
+#[derive(Clone, Debug, Reflect, Deserialize)]
+pub struct ItemDef {
+    /// 物品的唯一标识。
+    pub id: String,
+    /// 物品名称的多语言 Key。
+    pub name: String,
+    /// 物品图标路径（相对于 `assets` 目录）。
+    pub icon: String,
+    /// 一个格子最多能放多少个。
+    pub max_stack: u32,
+}
+
+impl ItemData {
+    /// 返回具有该标识的物品。
+    pub fn get(&self, id: &str) -> Option<&ItemDef> {
+        self.items.iter().find(|item| item.id == id)
+    }
+}
+
+impl RonAsset for ItemData {
+    const EXTENSIONS: &'static [&'static str] = &["items.ron"];
+
+    fn validate(&self) -> Result<(), String> {
+        let mut ids = HashSet::new();
+        for (index, item) in self.items.iter().enumerate() {
+            if item.id.is_empty() {
+                return Err(format!("item #{index} has an empty `id`"));
+            }
+            if !ids.insert(item.id.as_str()) {
+                return Err(format!("duplicate item id `{}`", item.id));
+            }
+            if item.name.is_empty() || item.icon.is_empty() {
+                return Err(format!("item `{}` has an empty `name` or `icon`", item.id));
+            }
+            if item.max_stack == 0 {
+                return Err(format!(
+                    "item `{}` must have a positive `max_stack`",
+                    item.id
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 一堆相同的物品。
+#[derive(Clone, Debug, PartialEq, Eq, Reflect, Deserialize)]
+pub struct ItemStack {
+    /// 物品标识，对应 [`ItemDef::id`]。
+    pub item: String,
+    pub count: u32,
+}
+
+/// 背包操作失败的原因。
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum InventoryError {
+    #[error("slot {0} does not exist")]
+    InvalidSlot(usize),
+    #[error("slot {0} is empty")]
+    EmptySlot(usize),
+    #[error("not enough `{item}`: needed {needed}, have {available}")]
+    NotEnough {
+        item: String,
+        needed: u32,
+        available: u32,
+    },
+    #[error("there is no empty slot")]
+    Full,
+}
+
+/// 背包。格子数量在创建时确定。
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component)]
+pub struct Inventory {
+    slots: Vec<Option<ItemStack>>,
+    /// 快捷栏中选中的格子。
+    pub selected: usize,
+}
+
+impl Inventory {
+    pub fn new(size: usize) -> Self {
+        Self {
+            slots: vec![None; size],
+            selected: 0,
+        }
+    }
+
+    /// 返回某个格子中的物品。
+    pub fn get(&self, slot: usize) -> Option<&ItemStack> {
+        self.slots.get(slot)?.as_ref()
+    }
+
+    /// 选择快捷栏中的下一个格子，到最后一格后回到第一格。背包比快捷栏小时只在背包的格子中循环。
+    pub fn select_next(&mut self) {
+        let slots = HOTBAR_SLOTS.min(self.slots.len()).max(1);
+        self.selected = (self.selected + 1) % slots;
+    }
+
+    /// 返回选中的格子中的物品。
+    pub fn selected_stack(&self) -> Option<&ItemStack> {
+        self.get(self.selected)
+    }
+
+    /// 某种物品的总数。
+    pub fn count(&self, item: &str) -> u32 {
+        self.slots
+            .iter()
+            .flatten()
+            .filter(|stack| stack.item == item)
+            .map(|stack| stack.count)
+            .sum()
+    }
+
+    /// 还能放入多少个该物品。未知物品为 0。
+    pub fn room_for(&self, items: &ItemData, item: &str) -> u32 {
+        let Some(max_stack) = items.get(item).map(|def| def.max_stack) else {
+            return 0;
+        };
+        self.slots
+            .iter()
+            .map(|slot| match slot {
+                Some(stack) if stack.item == item => max_stack.saturating_sub(stack.count),
+                Some(_) => 0,
+                None => max_stack,
+            })
+            .sum()
+    }
+
+    /// 放入物品，优先补满已有的同类物品堆，再使用空格子。返回放不下的数量；未知物品全部放不下。
+    pub fn add(&mut self, items: &ItemData, item: &str, count: u32) -> u32 {
+        let Some(max_stack) = items.get(item).map(|def| def.max_stack) else {
+            return count;
+        };
+        let mut rest = count;
+        for stack in self.slots.iter_mut().flatten() {
+            if stack.item == item {
+                let moved = rest.min(max_stack.saturating_sub(stack.count));
+                stack.count += moved;
+                rest -= moved;
+            }
+        }
+        for slot in self.slots.iter_mut().filter(|slot| slot.is_none()) {
+            if rest == 0 {
+                break;
+            }
+            let moved = rest.min(max_stack);
+            *slot = Some(ItemStack {
+                item: item.to_string(),
+                count: moved,
+            });
+            rest -= moved;
+        }
+        rest
+    }
+
+    /// 取出 `count` 个物品，优先从靠后的格子中取。数量不足时不取出任何物品。
+    pub fn remove(&mut self, item: &str, count: u32) -> Result<(), InventoryError> {
+        let available = self.count(item);
+        if available < count {
+            return Err(InventoryError::NotEnough {
+                item: item.to_string(),
+                needed: count,
+                available,
+            });
+        }
+        let mut rest = count;
+        for slot in self.slots.iter_mut().rev() {
+            if let Some(stack) = slot
+                && stack.item == item
+            {
+                let taken = rest.min(stack.count);
+                stack.count -= taken;
+                rest -= taken;
+                if stack.count == 0 {
+                    *slot = None;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 从某个格子中取出 `count` 个物品。数量不足时不取出任何物品。
+    pub fn take(&mut self, slot: usize, count: u32) -> Result<ItemStack, InventoryError> {
+        let entry = self
+            .slots
+            .get_mut(slot)
+            .ok_or(InventoryError::InvalidSlot(slot))?;
+        let stack = entry.as_mut().ok_or(InventoryError::EmptySlot(slot))?;
+        if stack.count < count {
+            return Err(InventoryError::NotEnough {
+                item: stack.item.clone(),
+                needed: count,
+                available: stack.count,
+            });
+        }
+        stack.count -= count;
+        let taken = ItemStack {
+            item: stack.item.clone(),
+            count,
+        };
+        if stack.count == 0 {
+            *entry = None;
+        }
+        Ok(taken)
+    }
+
+    /// 从某个格子中拆出 `count` 个物品放到第一个空格子中，返回新格子的索引。
+    /// 拆出的数量必须少于该格子中的数量。
+    pub fn split(&mut self, slot: usize, count: u32) -> Result<usize, InventoryError> {
+        let stack = self
+            .slots
+            .get(slot)
+            .ok_or(InventoryError::InvalidSlot(slot))?
+            .as_ref()
+            .ok_or(InventoryError::EmptySlot(slot))?;
+        if stack.count <= count {
+            return Err(InventoryError::NotEnough {
+                item: stack.item.clone(),
+                needed: count + 1,
+                available: stack.count,
+            });
+        }
+        let empty = self
+            .slots
+            .iter()
+            .position(Option::is_none)
+            .ok_or(InventoryError::Full)?;
+        let taken = self.take(slot, count)?;
+        self.slots[empty] = Some(taken);
+        Ok(empty)
+    }
+
+    /// 把 `from` 格子中的物品移动到 `to` 格子中。`to` 为空时整堆移动，物品不同时交换两个格子，
+    /// 物品相同时合并到堆叠上限。返回留在 `from` 格子中的数量。
+    pub fn merge(
+        &mut self,
+        items: &ItemData,
+        from: usize,
+        to: usize,
+    ) -> Result<u32, InventoryError> {
+        for slot in [from, to] {
+            if slot >= self.slots.len() {
+                return Err(InventoryError::InvalidSlot(slot));
+            }
+        }
+        let source = self.slots[from]
+            .as_ref()
+            .ok_or(InventoryError::EmptySlot(from))?;
+        if from == to {
+            return Ok(source.count);
+        }
+        match &self.slots[to] {
+            Some(target) if target.item == source.item => {
+                let max_stack = items.get(&source.item).map_or(0, |def| def.max_stack);
+                let moved = source.count.min(max_stack.saturating_sub(target.count));
+                let taken = self.take(from, moved)?;
+                if let Some(target) = &mut self.slots[to] {
+                    target.count += taken.count;
+                }
+                Ok(self.slots[from].as_ref().map_or(0, |stack| stack.count))
+            }
+            _ => {
+                self.slots.swap(from, to);
+                Ok(self.slots[from].as_ref().map_or(0, |stack| stack.count))
+            }
+        }
+    }
+}
+
+/// 快捷栏中的一个格子，对应 [`Inventory`] 中的索引。
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct HotbarSlot(usize);
+
+/// 快捷栏格子中的物品图标。
+#[derive(Component)]
+struct HotbarIcon(usize);
+
+/// 快捷栏格子中的物品数量。
+#[derive(Component)]
+struct HotbarCount(usize);
+
+/// 显示玩家背包前 [`HOTBAR_SLOTS`] 个格子的快捷栏。
+pub fn hotbar(font: Handle<Font>) -> impl Bundle {
+    (
+        widget::hud_bar("Hotbar"),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            for index in 0..HOTBAR_SLOTS {
+                parent
+                    .spawn(hotbar_slot(index, font.clone()))
+                    .observe(click_hotbar_slot);
+            }
+        })),
+    )
+}
+
+fn hotbar_slot(index: usize, font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new(format!("Hotbar Slot {index}")),
+        HotbarSlot(index),
+        Button,
+        Node {
+            width: Px(64.0),
+            height: Px(64.0),
+            border: UiRect::all(Px(3.0)),
+            ..default()
+        },
+        BorderColor::all(Color::NONE),
+        BackgroundColor(ui_palette::BUTTON_BACKGROUND),
+        InteractionPalette {
+            none: ui_palette::BUTTON_BACKGROUND,
+            hovered: ui_palette::BUTTON_HOVERED_BACKGROUND,
+            pressed: ui_palette::BUTTON_PRESSED_BACKGROUND,
+        },
+        children![
+            (
+                HotbarIcon(index),
+                ImageNode::default(),
+                Node {
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    ..default()
+                },
+                Visibility::Hidden,
+                Pickable::IGNORE,
+            ),
+            (
+                HotbarCount(index),
+                Text::default(),
+                TextFont::from_font_size(16.0).with_font(font),
+                TextColor(ui_palette::BUTTON_TEXT),
+                Node {
+                    position_type: PositionType::Absolute,
+                    right: Px(2.0),
+                    bottom: Px(0.0),
+                    ..default()
+                },
+                Pickable::IGNORE,
+            ),
+        ],
+    )
+}
+
+/// 左键选择格子，右键移动、合并或拆分物品。
+fn click_hotbar_slot(
+    click: On<Pointer<Click>>,
+    slot_query: Query<&HotbarSlot>,
+    items: Option<Res<ItemData>>,
+    mut inventory: Single<&mut Inventory, With<Player>>,
+) {
+    let Ok(HotbarSlot(index)) = slot_query.get(click.entity) else {
+        return;
+    };
+    match click.button {
+        PointerButton::Primary => inventory.selected = *index,
+        PointerButton::Secondary if *index == inventory.selected => {
+            let half = inventory.get(*index).map_or(0, |stack| stack.count / 2);
+            if half > 0
+                && let Err(error) = inventory.split(*index, half)
+            {
+                info!("Cannot split slot {index}: {error}");
+            }
+        }
+        PointerButton::Secondary => {
+            let Some(items) = items else {
+                return;
+            };
+            let from = inventory.selected;
+            match inventory.merge(&items, from, *index) {
+                Ok(_) => inventory.selected = *index,
+                Err(error) => info!("Cannot move slot {from} to {index}: {error}"),
+            }
+        }
+        _ => {}
+    }
+}
+
+fn cycle_hotbar_selection(mut inventory_query: Query<&mut Inventory, With<Player>>) {
+    for mut inventory in &mut inventory_query {
+        inventory.select_next();
+    }
+}
+
+/// 背包发生变化时更新快捷栏。
+fn update_hotbar(
+    asset_server: Res<AssetServer>,
+    items: Res<ItemData>,
+    inventory: Single<&Inventory, (With<Player>, Changed<Inventory>)>,
+    mut slot_query: Query<(&HotbarSlot, &mut BorderColor)>,
+    mut icon_query: Query<(&HotbarIcon, &mut ImageNode, &mut Visibility)>,
+    mut count_query: Query<(&HotbarCount, &mut Text)>,
+) {
+    for (slot, mut border) in &mut slot_query {
+        *border = if inventory.selected == slot.0 {
+            BorderColor::all(ui_palette::HEADER_TEXT)
+        } else {
+            BorderColor::all(Color::NONE)
+        };
+    }
+    for (icon, mut image, mut visibility) in &mut icon_query {
+        match inventory
+            .get(icon.0)
+            .and_then(|stack| items.get(&stack.item))
+        {
+            Some(def) => {
+                image.image = asset_server.load(&def.icon);
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+    for (count, mut text) in &mut count_query {
+        let value = inventory
+            .get(count.0)
+            .filter(|stack| stack.count > 1)
+            .map(|stack| stack.count.to_string())
+            .unwrap_or_default();
+        if text.0 != value {
+            text.0 = value;
+        }
+    }
+}
+
+/// 物品目录引用了不存在的多语言 Key 时发出警告。
+fn check_item_references(items: Res<ItemData>, lang_res: Res<LanguageRes>) {
+    for item in &items.items {
+        if !lang_res.contains(&item.name) {
+            warn!(
+                "Item `{}` references missing text key `{}`",
+                item.id, item.name
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> ItemData {
+        let item = |id: &str, max_stack| ItemDef {
+            id: id.to_string(),
+            name: String::new(),
+            icon: String::new(),
+            max_stack,
+        };
+        ItemData {
+            items: vec![item("seeds", 10), item("pumpkin", 1)],
+        }
+    }
+
+    #[test]
+    fn test_add_and_take() {
+        let items = items();
+        let mut inventory = Inventory::new(3);
+        assert_eq!(inventory.add(&items, "seeds", 15), 0);
+        assert_eq!(inventory.add(&items, "pumpkin", 2), 1);
+        assert_eq!(inventory.add(&items, "seeds", 1), 0);
+        assert_eq!(inventory.add(&items, "unknown", 1), 1);
+        assert_eq!(inventory.get(1).map(|stack| stack.count), Some(6));
+
+        assert!(inventory.take(1, 7).is_err());
+        assert_eq!(inventory.get(1).map(|stack| stack.count), Some(6));
+        assert_eq!(
+            inventory.take(1, 6),
+            Ok(ItemStack {
+                item: "seeds".to_string(),
+                count: 6,
+            })
+        );
+        assert_eq!(inventory.get(1), None);
+        assert_eq!(inventory.take(1, 1), Err(InventoryError::EmptySlot(1)));
+
+        assert_eq!(inventory.room_for(&items, "seeds"), 10);
+        assert!(inventory.remove("seeds", 11).is_err());
+        assert_eq!(inventory.remove("seeds", 4), Ok(()));
+        assert_eq!(inventory.count("seeds"), 6);
+    }
+
+    #[test]
+    fn test_split_and_merge() {
+        let items = items();
+        let mut inventory = Inventory::new(3);
+        inventory.add(&items, "seeds", 8);
+        assert_eq!(
+            inventory.split(0, 8),
+            Err(InventoryError::NotEnough {
+                item: "seeds".to_string(),
+                needed: 9,
+                available: 8,
+            })
+        );
+        assert_eq!(inventory.split(0, 3), Ok(1));
+        inventory.add(&items, "seeds", 6);
+        assert_eq!(inventory.get(0).map(|stack| stack.count), Some(10));
+        assert_eq!(inventory.get(1).map(|stack| stack.count), Some(4));
+
+        // 合并到堆叠上限，剩余的留在原来的格子中。
+        assert_eq!(inventory.merge(&items, 0, 1), Ok(4));
+        assert_eq!(inventory.get(1).map(|stack| stack.count), Some(10));
+        // 目标为空时整堆移动。
+        assert_eq!(inventory.merge(&items, 0, 2), Ok(0));
+        assert_eq!(inventory.get(0), None);
+        assert_eq!(
+            inventory.merge(&items, 0, 1),
+            Err(InventoryError::EmptySlot(0))
+        );
+    }
+
+    #[test]
+    fn test_select_next() {
+        // 背包比快捷栏小时，只在存在的格子中循环。
+        let mut inventory = Inventory::new(3);
+        let selected = (0..4)
+            .map(|_| {
+                inventory.select_next();
+                inventory.selected
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(selected, [1, 2, 0, 1]);
+
+        // 背包比快捷栏大时，只在快捷栏中循环。
+        let mut inventory = Inventory::new(HOTBAR_SLOTS * 2);
+        inventory.selected = HOTBAR_SLOTS - 1;
+        inventory.select_next();
+        assert_eq!(inventory.selected, 0);
+    }
+}
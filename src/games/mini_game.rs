@@ -0,0 +1,202 @@
+//! 小游戏注册表。
+//!
+//! 游戏目录（[`GameList`](crate::games::game_list::GameList)）中的每个条目通过 `plugin`
+//! 字段引用一个实现了 [`MiniGame`] 的插件。打开游戏时会进入对应的 [`ActiveGame`] 状态，
+//! 游戏的系统应以该状态为运行条件，生成的实体应使用 `DespawnOnExit(G::state())`，
+//! 这样退出游戏时只会清理该游戏自己的内容。
+//!
+//! 游戏运行时可以在 [`RunStats`] 中记录统计数据。分出胜负时应发送 [`GameOver`] 消息，
+//! 由这里统一暂停游戏并打开结算菜单。
+
+use std::time::Duration;
+
+use bevy::{platform::collections::HashSet, prelude::*};
+
+use crate::{AppSystems, PausableSystems, Pause, menus::Menu, screens::Screen};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<MiniGameRegistry>();
+    app.init_resource::<SelectedGame>();
+    app.add_sub_state::<ActiveGame>();
+    app.init_resource::<RunStats>();
+    app.add_message::<GameOver>();
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (reset_run_stats, start_selected_game),
+    );
+    app.add_systems(
+        Update,
+        (
+            tick_run_time
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+            end_game.after(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// 一个小游戏。
+pub trait MiniGame {
+    /// 插件标识，与游戏目录中的 `plugin` 字段对应。
+    const ID: &'static str;
+
+    /// 该游戏能否保存。只有把需要的状态都通过 [`RegisterSaved`](crate::save::RegisterSaved)
+    /// 注册并标记为 [`Saved`](crate::save::Saved) 的游戏才能保存，否则读取存档时只会恢复部分状态。
+    const SAVEABLE: bool = false;
+
+    /// 注册该游戏的资源和系统。
+    fn plugin(app: &mut App);
+
+    /// 该游戏运行时的 [`ActiveGame`] 状态。
+    fn state() -> ActiveGame {
+        ActiveGame::Running(Self::ID)
+    }
+}
+
+pub trait RegisterMiniGame {
+    /// 注册一个小游戏，使游戏目录可以通过 [`MiniGame::ID`] 引用它。
+    fn register_mini_game<G: MiniGame>(&mut self) -> &mut Self;
+}
+
+impl RegisterMiniGame for App {
+    fn register_mini_game<G: MiniGame>(&mut self) -> &mut Self {
+        let mut registry = self.world_mut().resource_mut::<MiniGameRegistry>();
+        if !registry.games.insert(G::ID) {
+            warn!("Mini game `{}` is registered more than once", G::ID);
+        }
+        if G::SAVEABLE {
+            registry.saveable.insert(G::ID);
+        }
+        G::plugin(self);
+        self
+    }
+}
+
+/// 已注册的小游戏。
+#[derive(Resource, Default)]
+pub struct MiniGameRegistry {
+    games: HashSet<&'static str>,
+    /// 可以保存的小游戏，见 [`MiniGame::SAVEABLE`]。
+    saveable: HashSet<&'static str>,
+}
+
+impl MiniGameRegistry {
+    /// 返回与 `id` 对应的已注册小游戏标识。
+    pub fn get(&self, id: &str) -> Option<&'static str> {
+        self.games.get(id).copied()
+    }
+
+    /// 该小游戏能否保存。
+    pub fn is_saveable(&self, id: &str) -> bool {
+        self.saveable.contains(id)
+    }
+}
+
+/// 下一次进入 [`Screen::Gameplay`] 时要启动的小游戏。
+#[derive(Resource, Default)]
+pub struct SelectedGame(pub Option<&'static str>);
+
+/// 当前正在运行的小游戏。仅在 [`Screen::Gameplay`] 中存在。
+#[derive(SubStates, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[source(Screen = Screen::Gameplay)]
+#[states(scoped_entities)]
+pub enum ActiveGame {
+    /// 尚未启动任何游戏。
+    #[default]
+    None,
+    /// 正在运行具有此插件标识的游戏。
+    Running(&'static str),
+}
+
+/// 一局游戏的结果。
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+pub enum GameOutcome {
+    Victory,
+    Defeat,
+}
+
+/// 当前这局游戏已经结束。
+#[derive(Message, Clone, Copy, Debug)]
+pub struct GameOver {
+    pub outcome: GameOutcome,
+}
+
+/// 本局游戏的统计数据，每次进入 [`Screen::Gameplay`] 时重置，在结算菜单中显示。
+#[derive(Resource, Default, Debug)]
+pub struct RunStats {
+    /// 本局游戏的结果，游戏结束前为 `None`。
+    pub outcome: Option<GameOutcome>,
+    /// 游戏进行的时间，不包括暂停的时间。
+    pub elapsed: Duration,
+    /// 各项统计数值，键为多语言 Key，按添加的顺序显示。
+    pub counters: Vec<(&'static str, u32)>,
+}
+
+impl RunStats {
+    /// 设置一项统计数值，不存在时添加到末尾。
+    pub fn set(&mut self, key: &'static str, value: u32) {
+        match self.counters.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value,
+            None => self.counters.push((key, value)),
+        }
+    }
+
+    /// 增加一项统计数值，不存在时添加到末尾。
+    pub fn add(&mut self, key: &'static str, amount: u32) {
+        let value = self.get(key) + amount;
+        self.set(key, value);
+    }
+
+    /// 返回一项统计数值，不存在时返回 0。
+    pub fn get(&self, key: &str) -> u32 {
+        self.counters
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map_or(0, |(_, v)| *v)
+    }
+}
+
+fn reset_run_stats(mut stats: ResMut<RunStats>) {
+    *stats = RunStats::default();
+}
+
+fn tick_run_time(
+    time: Res<Time>,
+    active_game: Res<State<ActiveGame>>,
+    mut stats: ResMut<RunStats>,
+) {
+    if *active_game.get() != ActiveGame::None && stats.outcome.is_none() {
+        stats.elapsed += time.delta();
+    }
+}
+
+fn start_selected_game(
+    selected_game: Res<SelectedGame>,
+    mut next_active_game: ResMut<NextState<ActiveGame>>,
+) {
+    match selected_game.0 {
+        Some(id) => next_active_game.set(ActiveGame::Running(id)),
+        None => warn!("Entered gameplay without a selected game"),
+    }
+}
+
+/// 记录游戏结果，暂停游戏并打开结算菜单。只处理每局游戏的第一个结果。
+fn end_game(
+    mut game_over: MessageReader<GameOver>,
+    active_game: Res<State<ActiveGame>>,
+    mut stats: ResMut<RunStats>,
+    mut next_pause: ResMut<NextState<Pause>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    let Some(GameOver { outcome }) = game_over.read().next() else {
+        return;
+    };
+    if stats.outcome.is_some() {
+        return;
+    }
+    info!("Game {:?} ended: {outcome:?}", active_game.get());
+    stats.outcome = Some(*outcome);
+    next_pause.set(Pause(true));
+    next_menu.set(Menu::Results);
+}
@@ -5,18 +5,39 @@
 
 use bevy::prelude::*;
 
+use crate::games::{
+    farming::Farming, mini_game::RegisterMiniGame, protect_the_land::ProtectTheLand,
+};
+
 mod animation;
+pub mod clock;
+mod farming;
 pub mod game_list;
-pub mod level;
+pub mod gold;
+pub mod grid;
+pub mod health;
+pub mod inventory;
+pub mod mini_game;
 mod movement;
 pub mod player;
+mod protect_the_land;
+pub mod shop;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         animation::plugin,
-        level::plugin,
+        clock::plugin,
+        mini_game::plugin,
         movement::plugin,
         player::plugin,
         game_list::plugin,
+        gold::plugin,
+        health::plugin,
+        inventory::plugin,
+        shop::plugin,
     ));
+
+    // 注册所有小游戏。游戏目录通过 `plugin` 字段引用这些游戏。
+    app.register_mini_game::<ProtectTheLand>();
+    app.register_mini_game::<Farming>();
 }
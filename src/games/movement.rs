@@ -12,11 +12,11 @@
 
 use bevy::{prelude::*, window::PrimaryWindow};
 
-use crate::{AppSystems, PausableSystems};
+use crate::{AppSystems, PausableSystems, save::RegisterSaved};
 
 pub(super) fn plugin(app: &mut App) {
-    app.register_type::<MovementController>();
-    app.register_type::<ScreenWrap>();
+    app.save_component::<MovementController>();
+    app.save_component::<ScreenWrap>();
 
     app.add_systems(
         Update,
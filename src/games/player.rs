@@ -1,7 +1,13 @@
 //! 玩家特定的行为。
+//!
+//! 玩家可以选择一种 [`Tool`]，然后按下动作键对面前的格子使用它。玩家只记录朝向并发送
+//! [`ToolUsed`] 消息，工具的具体效果由小游戏决定。
+
+use std::time::Duration;
 
 use bevy::{
     image::{ImageLoaderSettings, ImageSampler},
+    input::common_conditions::input_just_pressed,
     prelude::*,
 };
 
@@ -12,19 +18,36 @@ use crate::{
         animation::PlayerAnimation,
         movement::{MovementController, ScreenWrap},
     },
+    i18n::config::{TOOL_HOE, TOOL_SEED_BAG, TOOL_SICKLE, TOOL_WATERING_CAN},
+    save::RegisterSaved,
 };
 
 pub(super) fn plugin(app: &mut App) {
-    app.register_type::<Player>();
+    app.save_component::<Player>();
+    app.save_component::<Facing>();
+    app.save_component::<ToolBelt>();
+    app.add_message::<ToolUsed>();
+    app.add_observer(restore_player_sprite);
 
     app.register_type::<PlayerAssets>();
     app.load_resource::<PlayerAssets>();
 
-    // 将方向输入记录为移动控制。
+    // 将方向输入记录为移动控制，并处理工具的选择和使用。
     app.add_systems(
         Update,
-        record_player_directional_input
-            .in_set(AppSystems::RecordInput)
+        (
+            tick_tool_cooldown.in_set(AppSystems::TickTimers),
+            (
+                record_player_directional_input,
+                update_facing,
+                select_tool,
+                use_tool.run_if(
+                    input_just_pressed(KeyCode::Space).or(input_just_pressed(KeyCode::KeyE)),
+                ),
+            )
+                .chain()
+                .in_set(AppSystems::RecordInput),
+        )
             .in_set(PausableSystems),
     );
 }
@@ -35,23 +58,16 @@ pub fn player(
     player_assets: &PlayerAssets,
     texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
 ) -> impl Bundle {
-    // 纹理图集是一种将单个图像拆分为相关图像网格的方法。
-    // 您可以在此示例中了解更多信息：https://github.com/bevyengine/bevy/blob/latest/examples/2d/texture_atlas.rs
-    let layout = TextureAtlasLayout::from_grid(UVec2::splat(32), 6, 2, Some(UVec2::splat(1)), None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
     let player_animation = PlayerAnimation::new();
 
     (
         Name::new("Player"),
         Player,
-        Sprite {
-            image: player_assets.ducky.clone(),
-            texture_atlas: Some(TextureAtlas {
-                layout: texture_atlas_layout,
-                index: player_animation.get_atlas_index(),
-            }),
-            ..default()
-        },
+        player_sprite(
+            player_assets,
+            texture_atlas_layouts,
+            player_animation.get_atlas_index(),
+        ),
         Transform::from_scale(Vec2::splat(8.0).extend(1.0)),
         MovementController {
             max_speed,
@@ -59,16 +75,134 @@ pub fn player(
         },
         ScreenWrap,
         player_animation,
+        Facing::default(),
+        ToolBelt::default(),
     )
 }
 
+fn player_sprite(
+    player_assets: &PlayerAssets,
+    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    index: usize,
+) -> Sprite {
+    // 纹理图集是一种将单个图像拆分为相关图像网格的方法。
+    // 您可以在此示例中了解更多信息：https://github.com/bevyengine/bevy/blob/latest/examples/2d/texture_atlas.rs
+    let layout = TextureAtlasLayout::from_grid(UVec2::splat(32), 6, 2, Some(UVec2::splat(1)), None);
+    Sprite {
+        image: player_assets.ducky.clone(),
+        texture_atlas: Some(TextureAtlas {
+            layout: texture_atlas_layouts.add(layout),
+            index,
+        }),
+        ..default()
+    }
+}
+
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
 #[reflect(Component)]
-struct Player;
+pub struct Player;
+
+/// 为从存档中读取的玩家补全精灵。动画会在下一次变化时更新纹理图集的索引。
+fn restore_player_sprite(
+    add: On<Add, Player>,
+    mut commands: Commands,
+    sprite_query: Query<(), With<Sprite>>,
+    player_assets: Res<PlayerAssets>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    if sprite_query.contains(add.entity) {
+        return;
+    }
+    commands.entity(add.entity).insert(player_sprite(
+        &player_assets,
+        &mut texture_atlas_layouts,
+        0,
+    ));
+}
+
+/// 角色面对的方向，总是上下左右之一。
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct Facing(pub IVec2);
+
+impl Default for Facing {
+    fn default() -> Self {
+        Self(IVec2::NEG_Y)
+    }
+}
+
+/// 玩家可以使用的工具。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum Tool {
+    /// 锄头：开垦土地。
+    #[default]
+    Hoe,
+    /// 水壶：浇水。
+    WateringCan,
+    /// 种子袋：播种。
+    SeedBag,
+    /// 镰刀：收获成熟的作物，清除枯萎的作物。
+    Sickle,
+}
+
+impl Tool {
+    /// 所有工具，按选择键的顺序排列。
+    pub const ALL: [Self; 4] = [Self::Hoe, Self::WateringCan, Self::SeedBag, Self::Sickle];
+
+    /// 工具名称的文本键。
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Hoe => TOOL_HOE,
+            Self::WateringCan => TOOL_WATERING_CAN,
+            Self::SeedBag => TOOL_SEED_BAG,
+            Self::Sickle => TOOL_SICKLE,
+        }
+    }
+}
+
+/// 玩家当前选择的工具。使用工具后需要等待一段时间才能再次行动。
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct ToolBelt {
+    pub selected: Tool,
+    cooldown: Timer,
+}
+
+impl ToolBelt {
+    /// 每次使用工具后的冷却时间。
+    const COOLDOWN: Duration = Duration::from_millis(400);
+
+    /// 是否正在使用工具。
+    pub fn is_busy(&self) -> bool {
+        !self.cooldown.is_finished()
+    }
+}
+
+impl Default for ToolBelt {
+    fn default() -> Self {
+        let mut cooldown = Timer::new(Self::COOLDOWN, TimerMode::Once);
+        cooldown.finish();
+        Self {
+            selected: Tool::default(),
+            cooldown,
+        }
+    }
+}
+
+/// 玩家对面前的位置使用了工具。
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ToolUsed {
+    pub tool: Tool,
+    /// 玩家面前的位置（世界坐标）。
+    pub target: Vec2,
+}
+
+/// 工具作用的位置与玩家之间的距离（以世界单位为单位）。
+const TOOL_REACH: f32 = 40.0;
 
 fn record_player_directional_input(
     input: Res<ButtonInput<KeyCode>>,
-    mut controller_query: Query<&mut MovementController, With<Player>>,
+    mut controller_query: Query<(&mut MovementController, Option<&ToolBelt>), With<Player>>,
 ) {
     // 收集方向输入。
     let mut intent = Vec2::ZERO;
@@ -89,9 +223,78 @@ fn record_player_directional_input(
     // 如果输入来自模拟摇杆，则应省略此步骤。
     let intent = intent.normalize_or_zero();
 
-    // 将移动意图应用于控制器。
-    for mut controller in &mut controller_query {
-        controller.intent = intent;
+    // 将移动意图应用于控制器。使用工具时不能移动。
+    for (mut controller, tool_belt) in &mut controller_query {
+        controller.intent = if tool_belt.is_some_and(ToolBelt::is_busy) {
+            Vec2::ZERO
+        } else {
+            intent
+        };
+    }
+}
+
+/// 根据移动意图更新朝向。斜向移动时以幅度较大的方向为准。
+fn update_facing(mut player_query: Query<(&MovementController, &mut Facing), With<Player>>) {
+    for (controller, mut facing) in &mut player_query {
+        let intent = controller.intent;
+        if intent == Vec2::ZERO {
+            continue;
+        }
+        let direction = if intent.x.abs() > intent.y.abs() {
+            IVec2::new(intent.x.signum() as i32, 0)
+        } else {
+            IVec2::new(0, intent.y.signum() as i32)
+        };
+        if facing.0 != direction {
+            facing.0 = direction;
+        }
+    }
+}
+
+fn tick_tool_cooldown(time: Res<Time>, mut tool_query: Query<&mut ToolBelt>) {
+    for mut tool_belt in &mut tool_query {
+        tool_belt.cooldown.tick(time.delta());
+    }
+}
+
+const TOOL_KEYS: [KeyCode; 4] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+];
+
+/// 按数字键选择工具。
+fn select_tool(
+    input: Res<ButtonInput<KeyCode>>,
+    mut tool_query: Query<&mut ToolBelt, With<Player>>,
+) {
+    let Some(tool) = TOOL_KEYS
+        .iter()
+        .zip(Tool::ALL)
+        .find_map(|(key, tool)| input.just_pressed(*key).then_some(tool))
+    else {
+        return;
+    };
+    for mut tool_belt in &mut tool_query {
+        tool_belt.selected = tool;
+    }
+}
+
+/// 对玩家面前的位置使用当前工具。
+fn use_tool(
+    mut player_query: Query<(&Transform, &Facing, &mut ToolBelt), With<Player>>,
+    mut tool_used: MessageWriter<ToolUsed>,
+) {
+    for (transform, facing, mut tool_belt) in &mut player_query {
+        if tool_belt.is_busy() {
+            continue;
+        }
+        tool_belt.cooldown.reset();
+        tool_used.write(ToolUsed {
+            tool: tool_belt.selected,
+            target: transform.translation.xy() + facing.0.as_vec2() * TOOL_REACH,
+        });
     }
 }
 
@@ -100,8 +303,6 @@ fn record_player_directional_input(
 pub struct PlayerAssets {
     #[dependency]
     ducky: Handle<Image>,
-    #[dependency]
-    pub steps: Vec<Handle<AudioSource>>,
 }
 
 impl FromWorld for PlayerAssets {
@@ -115,12 +316,6 @@ impl FromWorld for PlayerAssets {
                     settings.sampler = ImageSampler::nearest();
                 },
             ),
-            steps: vec![
-                assets.load("audio/sound_effects/step1.ogg"),
-                assets.load("audio/sound_effects/step2.ogg"),
-                assets.load("audio/sound_effects/step3.ogg"),
-                assets.load("audio/sound_effects/step4.ogg"),
-            ],
         }
     }
 }
@@ -0,0 +1,39 @@
+//! 本局的金币。
+//!
+//! 每局开始时获得关卡文件中的初始金币，消灭敌人可以获得该敌人的赏金，
+//! 建造和升级防御塔需要花费金币。
+
+use bevy::prelude::*;
+
+use crate::{
+    games::{
+        gold::Gold,
+        health::Died,
+        mini_game::{MiniGame, RunStats},
+        protect_the_land::{ProtectTheLand, enemy::Enemy},
+    },
+    i18n::config::PROTECT_THE_LAND_GOLD_EARNED,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(collect_bounty);
+
+    app.add_systems(OnExit(ProtectTheLand::state()), remove_gold);
+}
+
+fn collect_bounty(
+    died: On<Died>,
+    enemy_query: Query<&Enemy>,
+    gold: Option<ResMut<Gold>>,
+    mut stats: ResMut<RunStats>,
+) {
+    let (Ok(enemy), Some(mut gold)) = (enemy_query.get(died.entity), gold) else {
+        return;
+    };
+    gold.0 += enemy.gold;
+    stats.add(PROTECT_THE_LAND_GOLD_EARNED, enemy.gold);
+}
+
+fn remove_gold(mut commands: Commands) {
+    commands.remove_resource::<Gold>();
+}
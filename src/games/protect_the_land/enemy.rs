@@ -0,0 +1,136 @@
+//! 敌人的定义和生成。
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    AppSystems, PausableSystems,
+    games::{
+        health::{Armor, DespawnOnDeath, Died, Health, Resistances},
+        mini_game::MiniGame,
+        movement::MovementController,
+        protect_the_land::ProtectTheLand,
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Enemy>();
+    app.register_type::<DeathEffect>();
+    app.add_observer(spawn_death_effect);
+
+    app.add_systems(
+        Update,
+        (
+            tick_death_effects.in_set(AppSystems::TickTimers),
+            update_death_effects.in_set(AppSystems::Update),
+        )
+            .run_if(in_state(ProtectTheLand::state()))
+            .in_set(PausableSystems),
+    );
+}
+
+/// 一种敌人的属性，在波次文件中定义。
+#[derive(Clone, Debug, Deserialize, Reflect)]
+pub struct EnemyDef {
+    /// 最大生命值。
+    pub health: f32,
+    /// 护甲，每次受到的伤害减少的数值。
+    #[serde(default)]
+    pub armor: f32,
+    /// 各种伤害类型的抗性。
+    #[serde(default)]
+    pub resistances: Resistances,
+    /// 到达农田时对农田造成的伤害。
+    pub damage: f32,
+    /// 被消灭时获得的金币。
+    pub gold: u32,
+    /// 每秒的移动速度（以世界单位为单位）。
+    pub speed: f32,
+    /// 精灵的边长（以世界单位为单位）。
+    pub size: f32,
+    /// 精灵的颜色（sRGB）。
+    pub color: (f32, f32, f32),
+}
+
+/// 一个敌人。
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Enemy {
+    /// 敌人类型，对应波次文件中 `enemies` 的键。
+    pub kind: String,
+    /// 到达农田时对农田造成的伤害。
+    pub damage: f32,
+    /// 被消灭时获得的金币。
+    pub gold: u32,
+}
+
+/// 一个敌人实体。
+pub fn enemy(kind: &str, def: &EnemyDef, position: Vec2) -> impl Bundle {
+    let (r, g, b) = def.color;
+    (
+        Name::new(format!("Enemy ({kind})")),
+        Enemy {
+            kind: kind.to_string(),
+            damage: def.damage,
+            gold: def.gold,
+        },
+        Health::new(def.health),
+        Armor(def.armor),
+        def.resistances.clone(),
+        DespawnOnDeath,
+        Sprite::from_color(Color::srgb(r, g, b), Vec2::splat(def.size)),
+        // 不阻挡在地图上放置防御塔。
+        Pickable::IGNORE,
+        Transform::from_translation(position.extend(1.0)),
+        MovementController {
+            max_speed: def.speed,
+            ..default()
+        },
+        DespawnOnExit(ProtectTheLand::state()),
+    )
+}
+
+/// 敌人死亡时留下的逐渐消失的残影。
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct DeathEffect(Timer);
+
+fn spawn_death_effect(
+    died: On<Died>,
+    mut commands: Commands,
+    enemy_query: Query<(&Sprite, &Transform), With<Enemy>>,
+) {
+    let Ok((sprite, transform)) = enemy_query.get(died.entity) else {
+        return;
+    };
+    commands.spawn((
+        Name::new("Death Effect"),
+        DeathEffect(Timer::from_seconds(0.3, TimerMode::Once)),
+        sprite.clone(),
+        *transform,
+        Pickable::IGNORE,
+        DespawnOnExit(ProtectTheLand::state()),
+    ));
+}
+
+fn tick_death_effects(time: Res<Time>, mut effect_query: Query<&mut DeathEffect>) {
+    for mut effect in &mut effect_query {
+        effect.0.tick(time.delta());
+    }
+}
+
+/// 残影逐渐放大并淡出，结束后移除。
+fn update_death_effects(
+    mut commands: Commands,
+    mut effect_query: Query<(Entity, &DeathEffect, &mut Sprite, &mut Transform)>,
+) {
+    for (entity, effect, mut sprite, mut transform) in &mut effect_query {
+        if effect.0.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let progress = effect.0.fraction();
+        sprite.color.set_alpha(1.0 - progress);
+        transform.scale = Vec3::splat(1.0 + progress);
+    }
+}
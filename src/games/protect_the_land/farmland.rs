@@ -0,0 +1,94 @@
+//! 需要保护的农田。
+//!
+//! 农田作为一个整体拥有生命值。敌人走到农田上时会对它造成伤害并消失，
+//! 农田的生命值耗尽时本局游戏失败。
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    games::{
+        health::{Damage, DamageKind, Died, Health, Invulnerability},
+        mini_game::{GameOutcome, GameOver, MiniGame},
+        protect_the_land::{
+            ProtectTheLand,
+            enemy::Enemy,
+            map::{LevelData, LevelMap, Tile},
+        },
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Farmland>();
+    app.add_observer(lose_on_farmland_destroyed);
+
+    app.add_systems(
+        Update,
+        damage_farmland
+            .run_if(in_state(ProtectTheLand::state()).and(resource_exists::<LevelMap>))
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// 农田。每个关卡只有一个。
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Farmland;
+
+/// 农田实体。
+pub fn farmland(level_data: &LevelData) -> impl Bundle {
+    (
+        Name::new("Farmland"),
+        Farmland,
+        Health::new(level_data.farmland_health),
+        Invulnerability::new(level_data.farmland_invulnerability),
+        DespawnOnExit(ProtectTheLand::state()),
+    )
+}
+
+/// 走进农田的敌人对农田造成伤害，然后消失。农田处于无敌时间内时，敌人会留在农田上等待，
+/// 无敌时间结束后再造成伤害，所以每个到达农田的敌人都会造成伤害。
+fn damage_farmland(
+    mut commands: Commands,
+    level_map: Res<LevelMap>,
+    farmland_query: Query<(Entity, Option<&Invulnerability>), With<Farmland>>,
+    enemy_query: Query<(Entity, &Enemy, &Transform)>,
+    mut damage_writer: MessageWriter<Damage>,
+) {
+    let Ok((farmland, invulnerability)) = farmland_query.single() else {
+        return;
+    };
+    let has_invulnerability = invulnerability.is_some();
+    if invulnerability.is_some_and(Invulnerability::is_active) {
+        return;
+    }
+    for (entity, enemy, transform) in &enemy_query {
+        let pos = level_map.layout.world_to_grid(transform.translation.xy());
+        if pos.and_then(|pos| level_map.tile(pos)) != Some(Tile::Farmland) {
+            continue;
+        }
+        damage_writer.write(Damage {
+            target: farmland,
+            amount: enemy.damage,
+            kind: DamageKind::Physical,
+        });
+        commands.entity(entity).despawn();
+        // 这次伤害会开始新的无敌时间，其他敌人要等它结束。
+        if has_invulnerability {
+            break;
+        }
+    }
+}
+
+fn lose_on_farmland_destroyed(
+    died: On<Died>,
+    farmland_query: Query<(), With<Farmland>>,
+    mut game_over: MessageWriter<GameOver>,
+) {
+    if farmland_query.contains(died.entity) {
+        game_over.write(GameOver {
+            outcome: GameOutcome::Defeat,
+        });
+    }
+}
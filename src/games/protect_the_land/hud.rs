@@ -0,0 +1,243 @@
+//! 游戏中的 HUD：农田生命值、金币、波次、下一波倒计时和防御塔建造栏。
+//!
+//! 每个标签只在对应的数据发生变化时更新，切换语言后由 [`LocalizedText`] 重新生成文本。
+
+use bevy::{ecs::spawn::SpawnWith, prelude::*, ui::Val::*};
+
+use crate::{
+    FntAssets,
+    games::{
+        gold::Gold,
+        health::Health,
+        mini_game::MiniGame,
+        protect_the_land::{
+            ProtectTheLand,
+            farmland::Farmland,
+            tower::{TowerData, TowerSelection},
+            waves::WaveScheduler,
+        },
+    },
+    i18n::{
+        LocalizedText, TextArg,
+        config::{
+            PROTECT_THE_LAND_BUILD_SLOT, PROTECT_THE_LAND_FARMLAND, PROTECT_THE_LAND_GOLD,
+            PROTECT_THE_LAND_NEXT_WAVE, PROTECT_THE_LAND_WAVE,
+        },
+    },
+    screens::Screen,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<HudLabel>();
+    app.register_type::<BuildSlot>();
+
+    app.add_systems(OnEnter(ProtectTheLand::state()), spawn_hud);
+    app.add_systems(
+        Update,
+        (
+            update_farmland_label,
+            update_gold_label.run_if(resource_exists_and_changed::<Gold>),
+            update_wave_labels.run_if(resource_exists_and_changed::<WaveScheduler>),
+            update_build_slots.run_if(resource_exists_and_changed::<TowerSelection>),
+        )
+            .run_if(in_state(ProtectTheLand::state())),
+    );
+}
+
+/// HUD 中的一个标签。
+#[derive(Component, Reflect, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+enum HudLabel {
+    Farmland,
+    Gold,
+    Wave,
+    NextWave,
+}
+
+/// 建造栏中的一个格子，对应 [`TowerData::towers`] 中的索引。
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct BuildSlot(usize);
+
+fn spawn_hud(mut commands: Commands, font_res: Res<FntAssets>, tower_data: Option<Res<TowerData>>) {
+    let font = font_res.pixel();
+    // 防御塔文件加载失败时建造栏为空。
+    let slots = tower_data
+        .map(|tower_data| {
+            (0..tower_data.towers.len())
+                .map(|index| (index, build_slot_text(index, &tower_data)))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let slot_font = font.clone();
+
+    commands.spawn((
+        widget::hud_root("HUD"),
+        DespawnOnExit(Screen::Gameplay),
+        children![
+            (
+                widget::hud_bar("Status Bar"),
+                children![
+                    widget::hud_label(
+                        HudLabel::Farmland,
+                        LocalizedText::new(PROTECT_THE_LAND_FARMLAND),
+                        font.clone()
+                    ),
+                    widget::hud_label(
+                        HudLabel::Gold,
+                        LocalizedText::new(PROTECT_THE_LAND_GOLD),
+                        font.clone()
+                    ),
+                    widget::hud_label(
+                        HudLabel::Wave,
+                        LocalizedText::new(PROTECT_THE_LAND_WAVE),
+                        font.clone()
+                    ),
+                    (
+                        widget::hud_label(
+                            HudLabel::NextWave,
+                            LocalizedText::new(PROTECT_THE_LAND_NEXT_WAVE),
+                            font.clone()
+                        ),
+                        Visibility::Hidden,
+                    ),
+                ],
+            ),
+            (
+                widget::hud_bar("Build Bar"),
+                Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+                    for (index, text) in slots {
+                        parent
+                            .spawn(build_slot(index, text, slot_font.clone()))
+                            .observe(select_build_slot);
+                    }
+                })),
+            ),
+        ],
+    ));
+}
+
+fn build_slot(index: usize, text: LocalizedText, font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new(format!("Build Slot {index}")),
+        BuildSlot(index),
+        Button,
+        Node {
+            width: Px(160.0),
+            height: Px(64.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            border: UiRect::all(Px(3.0)),
+            ..default()
+        },
+        BorderColor::all(Color::NONE),
+        BackgroundColor(ui_palette::BUTTON_BACKGROUND),
+        InteractionPalette {
+            none: ui_palette::BUTTON_BACKGROUND,
+            hovered: ui_palette::BUTTON_HOVERED_BACKGROUND,
+            pressed: ui_palette::BUTTON_PRESSED_BACKGROUND,
+        },
+        children![(
+            text,
+            TextFont::from_font_size(18.0).with_font(font),
+            TextLayout::new_with_justify(Justify::Center),
+            TextColor(ui_palette::BUTTON_TEXT),
+            Pickable::IGNORE,
+        )],
+    )
+}
+
+/// 建造栏格子中的文本：快捷键、防御塔的名称和建造费用。
+fn build_slot_text(index: usize, tower_data: &TowerData) -> LocalizedText {
+    let def = &tower_data.towers[index];
+    LocalizedText::new(PROTECT_THE_LAND_BUILD_SLOT)
+        .with_arg("n", index + 1)
+        .with_arg("tower", TextArg::Key(def.name.clone().into()))
+        .with_arg("cost", def.tiers[0].cost)
+}
+
+/// 点击建造栏中的格子选择要建造的防御塔，再次点击取消选择。
+fn select_build_slot(
+    click: On<Pointer<Click>>,
+    slot_query: Query<&BuildSlot>,
+    mut selection: ResMut<TowerSelection>,
+) {
+    if let Ok(BuildSlot(index)) = slot_query.get(click.entity) {
+        selection.0 = (selection.0 != Some(*index)).then_some(*index);
+    }
+}
+
+fn update_farmland_label(
+    farmland_query: Query<&Health, (With<Farmland>, Changed<Health>)>,
+    mut label_query: Query<(&HudLabel, &mut LocalizedText)>,
+) {
+    for health in &farmland_query {
+        let text = LocalizedText::new(PROTECT_THE_LAND_FARMLAND)
+            .with_arg("health", health.current.max(0.0).ceil() as u32)
+            .with_arg("max", health.max.ceil() as u32);
+        for (_, mut label) in label_query
+            .iter_mut()
+            .filter(|(l, _)| **l == HudLabel::Farmland)
+        {
+            label.set_if_neq(text.clone());
+        }
+    }
+}
+
+fn update_gold_label(gold: Res<Gold>, mut label_query: Query<(&HudLabel, &mut LocalizedText)>) {
+    let text = LocalizedText::new(PROTECT_THE_LAND_GOLD).with_arg("gold", gold.0);
+    for (_, mut label) in label_query
+        .iter_mut()
+        .filter(|(l, _)| **l == HudLabel::Gold)
+    {
+        label.set_if_neq(text.clone());
+    }
+}
+
+/// 更新当前波次和下一波倒计时。不在倒计时中时隐藏倒计时标签。
+fn update_wave_labels(
+    scheduler: Res<WaveScheduler>,
+    mut label_query: Query<(&HudLabel, &mut LocalizedText, &mut Visibility)>,
+) {
+    let countdown = scheduler.countdown();
+    for (label, mut text, mut visibility) in &mut label_query {
+        match label {
+            HudLabel::Wave => {
+                text.set_if_neq(
+                    LocalizedText::new(PROTECT_THE_LAND_WAVE)
+                        .with_arg("n", scheduler.wave_number())
+                        .with_arg("total", scheduler.total_waves()),
+                );
+            }
+            HudLabel::NextWave => {
+                if let Some(seconds) = countdown {
+                    text.set_if_neq(
+                        LocalizedText::new(PROTECT_THE_LAND_NEXT_WAVE)
+                            .with_arg("seconds", seconds.ceil() as i64),
+                    );
+                }
+                visibility.set_if_neq(if countdown.is_some() {
+                    Visibility::Inherited
+                } else {
+                    Visibility::Hidden
+                });
+            }
+            HudLabel::Farmland | HudLabel::Gold => {}
+        }
+    }
+}
+
+/// 高亮显示当前选择的防御塔。
+fn update_build_slots(
+    selection: Res<TowerSelection>,
+    mut slot_query: Query<(&BuildSlot, &mut BorderColor)>,
+) {
+    for (slot, mut border) in &mut slot_query {
+        *border = if selection.0 == Some(slot.0) {
+            BorderColor::all(ui_palette::HEADER_TEXT)
+        } else {
+            BorderColor::all(Color::NONE)
+        };
+    }
+}
@@ -0,0 +1,78 @@
+//! 生成主关卡。
+
+use bevy::prelude::*;
+
+use crate::{
+    asset_tracking::LoadResource,
+    audio::AddStateMusic,
+    games::{
+        gold::Gold,
+        mini_game::MiniGame,
+        protect_the_land::{
+            ProtectTheLand,
+            farmland::farmland,
+            map::{LevelData, LevelMap, map_tiles},
+            waves::{WaveData, WaveScheduler},
+        },
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<LevelAssets>();
+    app.load_resource::<LevelAssets>();
+
+    app.add_state_music(
+        ProtectTheLand::state(),
+        &["audio/music/Fluffing A Duck.ogg"],
+    );
+
+    app.add_systems(OnEnter(ProtectTheLand::state()), spawn_level);
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct LevelAssets {
+    #[dependency]
+    map: Handle<LevelData>,
+    #[dependency]
+    waves: Handle<WaveData>,
+}
+
+impl FromWorld for LevelAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            map: assets.load("levels/protect_the_land.level.ron"),
+            waves: assets.load("levels/protect_the_land.waves.ron"),
+        }
+    }
+}
+
+/// 一个系统，用于生成主关卡。
+fn spawn_level(
+    mut commands: Commands,
+    level_assets: Res<LevelAssets>,
+    level_data: Res<Assets<LevelData>>,
+    wave_data: Res<Assets<WaveData>>,
+) {
+    let (Some(level_data), Some(wave_data)) = (
+        level_data.get(&level_assets.map),
+        wave_data.get(&level_assets.waves),
+    ) else {
+        error!("Level data is not loaded");
+        return;
+    };
+    let level_map = LevelMap::from_data(level_data);
+
+    commands.spawn((
+        Name::new("Level"),
+        Transform::default(),
+        Visibility::default(),
+        DespawnOnExit(ProtectTheLand::state()),
+        children![map_tiles(&level_map)],
+    ));
+    commands.spawn(farmland(level_data));
+    commands.insert_resource(Gold(level_data.starting_gold));
+    commands.insert_resource(level_map);
+    commands.insert_resource(WaveScheduler::new(wave_data.clone()));
+}
@@ -0,0 +1,221 @@
+//! 塔防地图：由关卡文件描述的网格世界。
+//!
+//! 关卡文件中的每一行是一行格子（从上到下），每个字符表示一种 [`Tile`]：
+//! - `.` 可建造的草地
+//! - `#` 道路
+//! - `S` 敌人出生点
+//! - `F` 需要保护的农田
+//! - `X` 障碍物
+
+use bevy::{ecs::spawn::SpawnIter, prelude::*};
+use serde::Deserialize;
+
+use crate::{
+    asset_tracking::{RonAsset, RonAssetLoader},
+    games::{grid::GridLayout, mini_game::MiniGame, protect_the_land::ProtectTheLand},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<LevelMap>();
+    app.register_type::<MapTile>();
+
+    app.init_asset::<LevelData>();
+    app.register_asset_loader(RonAssetLoader::<LevelData>::default());
+
+    app.add_systems(OnExit(ProtectTheLand::state()), remove_level_map);
+}
+
+/// 关卡文件的内容。
+#[derive(Asset, TypePath, Deserialize)]
+pub struct LevelData {
+    /// 每个格子的边长（以世界单位为单位）。
+    pub tile_size: f32,
+    /// 地图的每一行（从上到下）。
+    pub rows: Vec<String>,
+    /// 每局开始时的金币。
+    pub starting_gold: u32,
+    /// 农田的生命值。
+    pub farmland_health: f32,
+    /// 农田每次受到伤害后的无敌时间（秒），避免成群的敌人瞬间摧毁农田。
+    #[serde(default)]
+    pub farmland_invulnerability: f32,
+}
+
+impl RonAsset for LevelData {
+    const EXTENSIONS: &'static [&'static str] = &["level.ron"];
+
+    fn validate(&self) -> Result<(), String> {
+        if self.tile_size <= 0.0 {
+            return Err(format!(
+                "`tile_size` must be positive, got {}",
+                self.tile_size
+            ));
+        }
+        if self.farmland_health <= 0.0 {
+            return Err(format!(
+                "`farmland_health` must be positive, got {}",
+                self.farmland_health
+            ));
+        }
+        let Some(width) = self.rows.first().map(|row| row.chars().count()) else {
+            return Err("the map has no rows".to_string());
+        };
+        for (y, row) in self.rows.iter().enumerate() {
+            if row.chars().count() != width {
+                return Err(format!("row {y} has a different width than row 0"));
+            }
+            if let Some(c) = row.chars().find(|c| Tile::from_char(*c).is_none()) {
+                return Err(format!("row {y} contains unknown tile `{c}`"));
+            }
+        }
+        let count = |tile: Tile| {
+            self.rows
+                .iter()
+                .flat_map(|row| row.chars())
+                .filter(|c| Tile::from_char(*c) == Some(tile))
+                .count()
+        };
+        if count(Tile::Spawn) == 0 {
+            return Err("the map has no spawn point (`S`)".to_string());
+        }
+        if count(Tile::Farmland) == 0 {
+            return Err("the map has no farmland (`F`)".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 地图格子的类型。
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+pub enum Tile {
+    /// 可以建造防御塔的草地。
+    Buildable,
+    /// 道路，敌人更喜欢沿着道路行走。
+    Path,
+    /// 敌人的出生点。
+    Spawn,
+    /// 需要保护的农田。
+    Farmland,
+    /// 无法通行也无法建造的障碍物。
+    Blocked,
+}
+
+impl Tile {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Buildable),
+            '#' => Some(Self::Path),
+            'S' => Some(Self::Spawn),
+            'F' => Some(Self::Farmland),
+            'X' => Some(Self::Blocked),
+            _ => None,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::Buildable => Color::srgb(0.376, 0.627, 0.251),
+            Self::Path => Color::srgb(0.757, 0.604, 0.420),
+            Self::Spawn => Color::srgb(0.486, 0.200, 0.200),
+            Self::Farmland => Color::srgb(0.431, 0.282, 0.157),
+            Self::Blocked => Color::srgb(0.400, 0.400, 0.431),
+        }
+    }
+}
+
+/// 当前关卡的网格地图。仅在游戏运行时存在。
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct LevelMap {
+    pub layout: GridLayout,
+    tiles: Vec<Tile>,
+    /// 格子上是否有障碍物（例如防御塔）。
+    obstacles: Vec<bool>,
+}
+
+impl LevelMap {
+    pub fn from_data(data: &LevelData) -> Self {
+        let size = UVec2::new(
+            data.rows.first().map_or(0, |row| row.chars().count()) as u32,
+            data.rows.len() as u32,
+        );
+        // 文件中的第一行位于地图顶部，而网格坐标的 `y` 向上增长。
+        let tiles = data
+            .rows
+            .iter()
+            .rev()
+            .flat_map(|row| row.chars())
+            .map(|c| Tile::from_char(c).unwrap_or(Tile::Blocked))
+            .collect::<Vec<_>>();
+        Self {
+            layout: GridLayout::new(size, data.tile_size),
+            obstacles: vec![false; tiles.len()],
+            tiles,
+        }
+    }
+
+    /// 返回网格坐标处的格子。
+    pub fn tile(&self, pos: IVec2) -> Option<Tile> {
+        self.layout.index(pos).map(|index| self.tiles[index])
+    }
+
+    /// 格子上是否有障碍物。
+    pub fn has_obstacle(&self, pos: IVec2) -> bool {
+        self.layout
+            .index(pos)
+            .is_some_and(|index| self.obstacles[index])
+    }
+
+    /// 设置格子上是否有障碍物。
+    pub fn set_obstacle(&mut self, pos: IVec2, obstacle: bool) {
+        if let Some(index) = self.layout.index(pos) {
+            self.obstacles[index] = obstacle;
+        }
+    }
+
+    /// 敌人是否可以通过该格子。
+    pub fn is_walkable(&self, pos: IVec2) -> bool {
+        self.tile(pos)
+            .is_some_and(|tile| tile != Tile::Blocked && !self.has_obstacle(pos))
+    }
+
+    /// 遍历所有指定类型的格子。
+    pub fn positions_of(&self, tile: Tile) -> impl Iterator<Item = IVec2> + '_ {
+        self.layout
+            .positions()
+            .filter(move |pos| self.tile(*pos) == Some(tile))
+    }
+}
+
+/// 地图格子的精灵。
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MapTile {
+    pub pos: IVec2,
+}
+
+/// 地图的所有格子精灵。
+pub fn map_tiles(level_map: &LevelMap) -> impl Bundle {
+    let layout = level_map.layout;
+    let tiles = layout
+        .positions()
+        .map(|pos| (pos, level_map.tile(pos).unwrap_or(Tile::Blocked)))
+        .collect::<Vec<_>>();
+    (
+        Name::new("Map"),
+        Transform::default(),
+        Visibility::default(),
+        Children::spawn(SpawnIter(tiles.into_iter().map(move |(pos, tile)| {
+            (
+                Name::new(format!("Tile {pos}")),
+                MapTile { pos },
+                Sprite::from_color(tile.color(), Vec2::splat(layout.tile_size - 2.0)),
+                Transform::from_translation(layout.grid_to_world(pos).extend(-10.0)),
+            )
+        }))),
+    )
+}
+
+fn remove_level_map(mut commands: Commands) {
+    commands.remove_resource::<LevelMap>();
+}
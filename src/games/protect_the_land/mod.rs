@@ -0,0 +1,42 @@
+//! “保护那块地!!”：一个简单的塔防游戏。
+
+use bevy::prelude::*;
+
+use crate::games::mini_game::MiniGame;
+
+pub mod economy;
+pub mod enemy;
+pub mod farmland;
+mod hud;
+mod level;
+pub mod map;
+pub mod pathfinding;
+pub mod projectile;
+mod stats;
+pub mod tower;
+pub mod waves;
+
+pub struct ProtectTheLand;
+
+impl MiniGame for ProtectTheLand {
+    const ID: &'static str = "protect_the_land";
+    // 防御塔、波次和农田的状态没有注册保存，保留默认的 `SAVEABLE = false`，
+    // 否则读取存档只会恢复共享的金币而重置其他状态。
+
+    fn plugin(app: &mut App) {
+        // `level` 在添加时就开始加载关卡和波次文件，
+        // 所以注册这些资源类型和加载器的 `map` 与 `waves` 必须在它之前添加。
+        app.add_plugins((map::plugin, waves::plugin));
+        app.add_plugins((
+            economy::plugin,
+            enemy::plugin,
+            farmland::plugin,
+            hud::plugin,
+            level::plugin,
+            pathfinding::plugin,
+            projectile::plugin,
+            stats::plugin,
+            tower::plugin,
+        ));
+    }
+}
@@ -0,0 +1,280 @@
+//! 敌人的寻路。
+//!
+//! 以所有农田格子为起点计算一张流场（[`FlowField`]），记录每个格子到达农田的代价。
+//! 敌人每一帧朝着代价更低的相邻格子移动，并通过 [`MovementController`] 复用现有的移动逻辑。
+//! 地图上的障碍物（[`GridObstacle`]）发生变化时会重新计算流场，
+//! 会完全堵死道路的障碍物则会被拒绝。
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    games::{
+        grid::GridLayout,
+        mini_game::MiniGame,
+        movement::MovementController,
+        protect_the_land::{
+            ProtectTheLand,
+            enemy::Enemy,
+            map::{LevelMap, Tile},
+        },
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<GridObstacle>();
+    app.register_type::<PlacedObstacle>();
+    app.add_observer(add_obstacle);
+    app.add_observer(remove_obstacle);
+
+    app.add_systems(
+        Update,
+        (
+            update_flow_field.run_if(resource_exists_and_changed::<LevelMap>),
+            steer_enemies.run_if(resource_exists::<FlowField>),
+        )
+            .chain()
+            .run_if(in_state(ProtectTheLand::state()))
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems),
+    );
+    app.add_systems(OnExit(ProtectTheLand::state()), remove_flow_field);
+}
+
+/// 占据一个格子、阻挡敌人通行的实体（例如防御塔）。
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct GridObstacle(pub IVec2);
+
+/// 标记已经在 [`LevelMap`] 中占据格子的 [`GridObstacle`]。
+/// 被拒绝的障碍物没有这个标记，移除时不会清除其他障碍物占据的格子。
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PlacedObstacle;
+
+/// 每个格子到达农田的代价。仅在游戏运行时存在。
+#[derive(Resource)]
+pub struct FlowField {
+    layout: GridLayout,
+    costs: Vec<Option<u32>>,
+}
+
+impl FlowField {
+    pub fn new(level_map: &LevelMap) -> Self {
+        Self::compute(level_map, None)
+    }
+
+    /// 计算流场，`extra_obstacle` 是额外视为障碍物的格子。
+    fn compute(level_map: &LevelMap, extra_obstacle: Option<IVec2>) -> Self {
+        let layout = level_map.layout;
+        let walkable = |pos: IVec2| level_map.is_walkable(pos) && Some(pos) != extra_obstacle;
+        let mut costs = vec![None; (layout.size.x * layout.size.y) as usize];
+        let mut queue = BinaryHeap::new();
+        for pos in level_map.positions_of(Tile::Farmland) {
+            if walkable(pos) {
+                queue.push(Reverse((0, pos.to_array())));
+            }
+        }
+
+        // Dijkstra：从相邻格子走进 `pos` 的代价由 `pos` 的格子类型决定。
+        while let Some(Reverse((cost, pos))) = queue.pop() {
+            let pos = IVec2::from_array(pos);
+            let Some(index) = layout.index(pos) else {
+                continue;
+            };
+            if costs[index].is_some() {
+                continue;
+            }
+            costs[index] = Some(cost);
+            for neighbor in layout.neighbors(pos) {
+                if walkable(neighbor)
+                    && layout
+                        .index(neighbor)
+                        .is_some_and(|index| costs[index].is_none())
+                {
+                    let step = step_cost(level_map.tile(pos).unwrap_or(Tile::Blocked));
+                    queue.push(Reverse((cost + step, neighbor.to_array())));
+                }
+            }
+        }
+
+        Self { layout, costs }
+    }
+
+    /// 格子到达农田的代价。如果无法到达，则返回 `None`。
+    pub fn cost(&self, pos: IVec2) -> Option<u32> {
+        self.layout.index(pos).and_then(|index| self.costs[index])
+    }
+
+    /// 从该格子出发，朝农田前进的下一个格子。已经在农田上或无法到达时返回 `None`。
+    pub fn next_step(&self, pos: IVec2) -> Option<IVec2> {
+        let cost = self.cost(pos)?;
+        self.layout
+            .neighbors(pos)
+            .filter_map(|neighbor| Some((self.cost(neighbor)?, neighbor)))
+            .filter(|(neighbor_cost, _)| *neighbor_cost < cost)
+            .min_by_key(|(neighbor_cost, _)| *neighbor_cost)
+            .map(|(_, neighbor)| neighbor)
+    }
+}
+
+/// 走进该类型格子的代价。敌人更喜欢沿着道路行走。
+/// 障碍物不会被加入寻路队列，因此它的代价无关紧要。
+fn step_cost(tile: Tile) -> u32 {
+    match tile {
+        Tile::Path | Tile::Spawn | Tile::Farmland => 2,
+        Tile::Buildable | Tile::Blocked => 3,
+    }
+}
+
+/// 在 `pos` 放置障碍物是否会使出生点或 `walkers`（例如敌人所在的格子）无法到达农田。
+pub fn blocks_path(
+    level_map: &LevelMap,
+    pos: IVec2,
+    walkers: impl IntoIterator<Item = IVec2>,
+) -> bool {
+    let flow_field = FlowField::compute(level_map, Some(pos));
+    level_map
+        .positions_of(Tile::Spawn)
+        .chain(walkers)
+        .any(|walker| walker == pos || flow_field.cost(walker).is_none())
+}
+
+fn add_obstacle(
+    add: On<Add, GridObstacle>,
+    mut commands: Commands,
+    obstacle_query: Query<&GridObstacle>,
+    enemy_query: Query<&Transform, With<Enemy>>,
+    level_map: Option<ResMut<LevelMap>>,
+) {
+    let (Ok(obstacle), Some(mut level_map)) = (obstacle_query.get(add.entity), level_map) else {
+        return;
+    };
+    let walkers = enemy_query
+        .iter()
+        .filter_map(|transform| level_map.layout.world_to_grid(transform.translation.xy()));
+    if blocks_path(&level_map, obstacle.0, walkers) {
+        warn!(
+            "Obstacle at {} would block the path, ignoring it",
+            obstacle.0
+        );
+        commands.entity(add.entity).remove::<GridObstacle>();
+        return;
+    }
+    level_map.set_obstacle(obstacle.0, true);
+    commands.entity(add.entity).insert(PlacedObstacle);
+}
+
+fn remove_obstacle(
+    remove: On<Remove, GridObstacle>,
+    mut commands: Commands,
+    obstacle_query: Query<&GridObstacle, With<PlacedObstacle>>,
+    level_map: Option<ResMut<LevelMap>>,
+) {
+    let Ok(obstacle) = obstacle_query.get(remove.entity) else {
+        return;
+    };
+    commands
+        .entity(remove.entity)
+        .try_remove::<PlacedObstacle>();
+    if let Some(mut level_map) = level_map {
+        level_map.set_obstacle(obstacle.0, false);
+    }
+}
+
+fn update_flow_field(mut commands: Commands, level_map: Res<LevelMap>) {
+    commands.insert_resource(FlowField::new(&level_map));
+}
+
+/// 将敌人的移动意图设置为朝向流场中的下一个格子。
+fn steer_enemies(
+    flow_field: Res<FlowField>,
+    level_map: Res<LevelMap>,
+    mut enemy_query: Query<(&Transform, &mut MovementController), With<Enemy>>,
+) {
+    let layout = level_map.layout;
+    for (transform, mut controller) in &mut enemy_query {
+        let position = transform.translation.xy();
+        let Some(pos) = layout.world_to_grid(position) else {
+            controller.intent = Vec2::ZERO;
+            continue;
+        };
+        // 到达农田后停在格子中心。
+        let target = layout.grid_to_world(flow_field.next_step(pos).unwrap_or(pos));
+        let offset = target - position;
+        controller.intent = if offset.length() < 1.0 {
+            Vec2::ZERO
+        } else {
+            offset.normalize()
+        };
+    }
+}
+
+fn remove_flow_field(mut commands: Commands) {
+    commands.remove_resource::<FlowField>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::protect_the_land::map::LevelData;
+
+    fn level_map(rows: &[&str]) -> LevelMap {
+        LevelMap::from_data(&LevelData {
+            tile_size: 1.0,
+            starting_gold: 0,
+            farmland_health: 1.0,
+            farmland_invulnerability: 0.0,
+            rows: rows.iter().map(|row| row.to_string()).collect(),
+        })
+    }
+
+    #[test]
+    fn test_flow_field_reaches_farmland() {
+        let map = level_map(&["S..F"]);
+        let flow_field = FlowField::new(&map);
+        assert_eq!(flow_field.cost(IVec2::new(3, 0)), Some(0));
+        assert_eq!(
+            flow_field.next_step(IVec2::new(0, 0)),
+            Some(IVec2::new(1, 0))
+        );
+        assert_eq!(flow_field.next_step(IVec2::new(3, 0)), None);
+    }
+
+    #[test]
+    fn test_flow_field_prefers_path() {
+        let map = level_map(&["...", "SXF", "###"]);
+        let flow_field = FlowField::new(&map);
+        // 上下两条路线一样长，敌人应该选择下方的道路。
+        assert_eq!(
+            flow_field.next_step(IVec2::new(0, 1)),
+            Some(IVec2::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn test_blocks_path() {
+        let map = level_map(&["SX.", "...", "XXF"]);
+        assert!(blocks_path(&map, IVec2::new(2, 1), []));
+        assert!(!blocks_path(&map, IVec2::new(2, 2), []));
+        assert!(blocks_path(&map, IVec2::new(1, 1), [IVec2::new(1, 1)]));
+    }
+
+    #[test]
+    fn test_rejected_obstacle_keeps_tile() {
+        let mut app = App::new();
+        app.add_observer(add_obstacle);
+        app.add_observer(remove_obstacle);
+        let mut map = level_map(&["S.F"]);
+        // 出生点上的障碍物会被拒绝，拒绝时不应清除格子上已有的障碍物标记。
+        map.set_obstacle(IVec2::ZERO, true);
+        app.insert_resource(map);
+
+        let entity = app.world_mut().spawn(GridObstacle(IVec2::ZERO)).id();
+        app.update();
+        assert!(!app.world().entity(entity).contains::<GridObstacle>());
+        assert!(app.world().resource::<LevelMap>().has_obstacle(IVec2::ZERO));
+    }
+}
@@ -0,0 +1,93 @@
+//! 防御塔发射的炮弹。
+//!
+//! 炮弹以固定速度飞向目标，命中时造成伤害。目标在命中前消失时，炮弹也会消失。
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    audio::{PlaySound, cues},
+    games::{
+        health::{Damage, DamageKind},
+        mini_game::MiniGame,
+        protect_the_land::{ProtectTheLand, enemy::Enemy},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Projectile>();
+
+    app.add_systems(
+        Update,
+        move_projectiles
+            .run_if(in_state(ProtectTheLand::state()))
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// 一发飞向目标的炮弹。
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Projectile {
+    pub target: Entity,
+    pub damage: f32,
+    pub kind: DamageKind,
+    /// 每秒的飞行速度（以世界单位为单位）。
+    pub speed: f32,
+}
+
+/// 一发炮弹实体。
+pub fn projectile(
+    origin: Vec2,
+    target: Entity,
+    damage: f32,
+    kind: DamageKind,
+    speed: f32,
+    color: Color,
+) -> impl Bundle {
+    (
+        Name::new("Projectile"),
+        Projectile {
+            target,
+            damage,
+            kind,
+            speed,
+        },
+        Sprite::from_color(color, Vec2::splat(8.0)),
+        Transform::from_translation(origin.extend(4.0)),
+        Pickable::IGNORE,
+        DespawnOnExit(ProtectTheLand::state()),
+    )
+}
+
+fn move_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectile_query: Query<(Entity, &Projectile, &mut Transform), Without<Enemy>>,
+    enemy_query: Query<&Transform, With<Enemy>>,
+    mut damage_writer: MessageWriter<Damage>,
+    mut sound_writer: MessageWriter<PlaySound>,
+) {
+    for (entity, projectile, mut transform) in &mut projectile_query {
+        let Ok(target_transform) = enemy_query.get(projectile.target) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let position = transform.translation.xy();
+        let offset = target_transform.translation.xy() - position;
+        let step = projectile.speed * time.delta_secs();
+        if offset.length() > step {
+            transform.translation += (offset.normalize() * step).extend(0.0);
+            continue;
+        }
+
+        commands.entity(entity).despawn();
+        damage_writer.write(Damage {
+            target: projectile.target,
+            amount: projectile.damage,
+            kind: projectile.kind,
+        });
+        sound_writer.write(PlaySound(cues::TOWER_HIT));
+    }
+}
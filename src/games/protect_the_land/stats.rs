@@ -0,0 +1,45 @@
+//! 在 [`RunStats`] 中记录本局的统计数据。
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems,
+    games::{
+        health::Died,
+        mini_game::{MiniGame, RunStats},
+        protect_the_land::{ProtectTheLand, enemy::Enemy, waves::WaveCleared},
+    },
+    i18n::config::{
+        PROTECT_THE_LAND_ENEMIES_KILLED, PROTECT_THE_LAND_GOLD_EARNED,
+        PROTECT_THE_LAND_WAVES_SURVIVED,
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(ProtectTheLand::state()), init_stats);
+    app.add_observer(count_kills);
+    app.add_systems(
+        Update,
+        count_waves
+            .run_if(in_state(ProtectTheLand::state()).and(on_message::<WaveCleared>))
+            .after(AppSystems::Update),
+    );
+}
+
+fn init_stats(mut stats: ResMut<RunStats>) {
+    stats.set(PROTECT_THE_LAND_WAVES_SURVIVED, 0);
+    stats.set(PROTECT_THE_LAND_ENEMIES_KILLED, 0);
+    stats.set(PROTECT_THE_LAND_GOLD_EARNED, 0);
+}
+
+fn count_kills(died: On<Died>, enemy_query: Query<(), With<Enemy>>, mut stats: ResMut<RunStats>) {
+    if enemy_query.contains(died.entity) {
+        stats.add(PROTECT_THE_LAND_ENEMIES_KILLED, 1);
+    }
+}
+
+fn count_waves(mut wave_cleared: MessageReader<WaveCleared>, mut stats: ResMut<RunStats>) {
+    for cleared in wave_cleared.read() {
+        stats.set(PROTECT_THE_LAND_WAVES_SURVIVED, cleared.wave as u32);
+    }
+}
@@ -0,0 +1,787 @@
+//! 防御塔：放置、索敌和开火。
+//!
+//! 按数字键选择要建造的防御塔，点击地图上的空草地花费金币进行放置，右键点击取消选择。
+//! 右键点击已放置的防御塔可以切换它的索敌优先级。左键点击已放置的防御塔将其选中，
+//! 然后按 `U` 花费金币升级到下一级，按 `Delete` 或 `Backspace` 出售并返还部分金币。
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    AppSystems, PausableSystems,
+    asset_tracking::{LoadResource, RonAsset, RonAssetLoader},
+    audio::{PlaySound, cues},
+    games::{
+        gold::Gold,
+        health::{DamageKind, Health},
+        mini_game::MiniGame,
+        protect_the_land::{
+            ProtectTheLand,
+            enemy::Enemy,
+            map::{LevelMap, MapTile, Tile},
+            pathfinding::{FlowField, GridObstacle, blocks_path},
+            projectile::projectile,
+        },
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Tower>();
+    app.register_type::<TowerData>();
+    app.register_type::<TowerSelection>();
+    app.register_asset_loader(RonAssetLoader::<TowerData>::default());
+    app.load_resource_from_path::<TowerData>("levels/protect_the_land.towers.ron");
+
+    app.add_systems(
+        OnEnter(ProtectTheLand::state()),
+        (init_tower_selection, spawn_tower_preview),
+    );
+    app.add_systems(OnExit(ProtectTheLand::state()), remove_tower_selection);
+    app.add_observer(place_tower);
+    app.add_observer(select_placed_tower);
+    app.add_observer(cycle_target_priority);
+    app.add_observer(move_tower_preview);
+
+    app.add_systems(
+        Update,
+        (
+            tick_tower_cooldowns.in_set(AppSystems::TickTimers),
+            (
+                select_tower,
+                clear_tower_selection.run_if(input_just_pressed(MouseButton::Right)),
+                upgrade_selected_tower.run_if(input_just_pressed(KeyCode::KeyU)),
+                sell_selected_tower.run_if(
+                    input_just_pressed(KeyCode::Delete).or(input_just_pressed(KeyCode::Backspace)),
+                ),
+            )
+                .in_set(AppSystems::RecordInput),
+            (fire_towers, update_tower_preview, update_tower_sprites).in_set(AppSystems::Update),
+        )
+            .run_if(
+                in_state(ProtectTheLand::state())
+                    .and(resource_exists::<TowerSelection>)
+                    .and(resource_exists::<TowerData>),
+            )
+            .in_set(PausableSystems),
+    );
+}
+
+/// 防御塔文件的内容。
+#[derive(Resource, Asset, Clone, Reflect, Deserialize)]
+#[reflect(Resource)]
+pub struct TowerData {
+    /// 出售防御塔时返还的金币比例（相对于建造和升级花费的总金币）。
+    pub sell_refund: f32,
+    /// 可建造的防御塔，按建造栏中的顺序排列。
+    pub towers: Vec<TowerDef>,
+}
+
+/// 一种防御塔的属性。
+#[derive(Clone, Debug, Reflect, Deserialize)]
+pub struct TowerDef {
+    /// 防御塔的唯一标识。
+    pub id: String,
+    /// 防御塔名称的多语言 Key。
+    pub name: String,
+    /// 防御塔的各个等级。第一级是建造时的属性，之后每一级都需要花费金币升级。
+    pub tiers: Vec<TowerTier>,
+    /// 炮弹的伤害类型。
+    #[serde(default)]
+    pub damage_kind: DamageKind,
+    /// 精灵的颜色（sRGB）。
+    pub color: (f32, f32, f32),
+    /// 新建造的防御塔的索敌优先级。
+    #[serde(default)]
+    pub priority: TargetPriority,
+}
+
+/// 防御塔某一级的属性。
+#[derive(Clone, Debug, Reflect, Deserialize)]
+pub struct TowerTier {
+    /// 建造（第一级）或升级到这一级所需的金币。
+    pub cost: u32,
+    /// 攻击范围（以世界单位为单位）。
+    pub range: f32,
+    /// 两次开火之间的间隔（秒）。
+    pub fire_interval: f32,
+    /// 每发炮弹的伤害。
+    pub damage: f32,
+    /// 炮弹每秒的飞行速度（以世界单位为单位）。
+    pub projectile_speed: f32,
+}
+
+/// 建造或升级防御塔失败的原因。
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TowerError {
+    #[error("not enough gold: costs {cost}, have {gold}")]
+    NotEnoughGold { cost: u32, gold: u32 },
+    #[error("tower `{0}` is already at the highest tier")]
+    MaxTier(String),
+}
+
+impl TowerDef {
+    fn color(&self) -> Color {
+        let (r, g, b) = self.color;
+        Color::srgb(r, g, b)
+    }
+
+    /// 花费第一级的金币建造一座防御塔。金币不足时金币不变。
+    pub fn build(&self, gold: &mut Gold) -> Result<Tower, TowerError> {
+        let tier = &self.tiers[0];
+        spend(gold, tier.cost)?;
+        Ok(Tower {
+            kind: self.id.clone(),
+            tier: 0,
+            invested: tier.cost,
+            priority: self.priority,
+            cooldown: Timer::from_seconds(tier.fire_interval, TimerMode::Once),
+        })
+    }
+}
+
+impl Tower {
+    /// 花费金币将防御塔升级到下一级。失败时防御塔和金币都不变。
+    pub fn upgrade(&mut self, def: &TowerDef, gold: &mut Gold) -> Result<(), TowerError> {
+        let next = def
+            .tiers
+            .get(self.tier + 1)
+            .ok_or_else(|| TowerError::MaxTier(def.id.clone()))?;
+        spend(gold, next.cost)?;
+        self.tier += 1;
+        self.invested += next.cost;
+        self.cooldown = Timer::from_seconds(next.fire_interval, TimerMode::Once);
+        Ok(())
+    }
+}
+
+fn spend(gold: &mut Gold, cost: u32) -> Result<(), TowerError> {
+    if gold.try_spend(cost) {
+        Ok(())
+    } else {
+        Err(TowerError::NotEnoughGold { cost, gold: gold.0 })
+    }
+}
+
+impl TowerData {
+    /// 返回具有该标识的防御塔。
+    pub fn get(&self, id: &str) -> Option<&TowerDef> {
+        self.towers.iter().find(|tower| tower.id == id)
+    }
+
+    /// 出售防御塔时返还的金币。
+    pub fn refund(&self, tower: &Tower) -> u32 {
+        (tower.invested as f32 * self.sell_refund) as u32
+    }
+}
+
+impl RonAsset for TowerData {
+    const EXTENSIONS: &'static [&'static str] = &["towers.ron"];
+
+    fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.sell_refund) {
+            return Err(format!(
+                "`sell_refund` must be between 0 and 1, got {}",
+                self.sell_refund
+            ));
+        }
+        if self.towers.is_empty() {
+            return Err("there are no towers".to_string());
+        }
+        for (index, tower) in self.towers.iter().enumerate() {
+            if self.towers[..index]
+                .iter()
+                .any(|other| other.id == tower.id)
+            {
+                return Err(format!("duplicate tower id `{}`", tower.id));
+            }
+            if tower.tiers.is_empty() {
+                return Err(format!("tower `{}` has no tiers", tower.id));
+            }
+            for (level, tier) in tower.tiers.iter().enumerate() {
+                if tier.range <= 0.0 || tier.fire_interval <= 0.0 || tier.projectile_speed <= 0.0 {
+                    return Err(format!(
+                        "tier {level} of tower `{}` must have a positive `range`, `fire_interval` and `projectile_speed`",
+                        tower.id
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 防御塔选择目标的方式。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect, Deserialize)]
+pub enum TargetPriority {
+    /// 最接近农田的敌人。
+    #[default]
+    First,
+    /// 离农田最远的敌人。
+    Last,
+    /// 生命值最高的敌人。
+    Strongest,
+    /// 离防御塔最近的敌人。
+    Closest,
+}
+
+impl TargetPriority {
+    fn next(self) -> Self {
+        match self {
+            Self::First => Self::Last,
+            Self::Last => Self::Strongest,
+            Self::Strongest => Self::Closest,
+            Self::Closest => Self::First,
+        }
+    }
+}
+
+/// 一座防御塔。
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Tower {
+    /// 防御塔类型，对应 [`TowerDef::id`]。
+    pub kind: String,
+    /// 当前等级（[`TowerDef::tiers`] 中的索引）。
+    pub tier: usize,
+    /// 建造和升级这座防御塔花费的总金币。
+    pub invested: u32,
+    pub priority: TargetPriority,
+    cooldown: Timer,
+}
+
+/// 一座防御塔实体。
+pub fn tower(def: &TowerDef, tower: Tower, pos: IVec2, level_map: &LevelMap) -> impl Bundle {
+    let size = tower_size(level_map, tower.tier);
+    (
+        Name::new(format!("Tower ({})", def.id)),
+        tower,
+        GridObstacle(pos),
+        Sprite::from_color(def.color(), Vec2::splat(size)),
+        Transform::from_translation(level_map.layout.grid_to_world(pos).extend(2.0)),
+        DespawnOnExit(ProtectTheLand::state()),
+    )
+}
+
+/// 防御塔精灵的边长，等级越高越大。
+fn tower_size(level_map: &LevelMap, tier: usize) -> f32 {
+    level_map.layout.tile_size * (0.6 + 0.1 * tier as f32).min(0.9)
+}
+
+/// 被选中的已放置的防御塔。
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SelectedTower;
+
+/// 当前选择要建造的防御塔（[`TowerData::towers`] 中的索引）。
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct TowerSelection(pub Option<usize>);
+
+/// 跟随鼠标显示将要建造的防御塔。
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct TowerPreview {
+    /// 鼠标所在的格子。
+    pos: Option<IVec2>,
+}
+
+/// 是否可以在该格子上建造防御塔。
+pub fn can_place_tower(
+    level_map: &LevelMap,
+    pos: IVec2,
+    walkers: impl IntoIterator<Item = IVec2>,
+) -> bool {
+    level_map.tile(pos) == Some(Tile::Buildable)
+        && !level_map.has_obstacle(pos)
+        && !blocks_path(level_map, pos, walkers)
+}
+
+/// 防御塔可以攻击的一个敌人。
+#[derive(Clone, Copy, Debug)]
+pub struct TargetCandidate {
+    pub entity: Entity,
+    pub position: Vec2,
+    /// 当前生命值。
+    pub health: f32,
+    /// 从敌人所在的格子到达农田的代价，越低离农田越近。不在流场中时为 `None`。
+    pub cost: Option<u32>,
+}
+
+/// 按优先级从攻击范围（含边界）内的敌人中选择目标，优先级相同时选择离防御塔最近的敌人。
+/// 不在流场中的敌人被视为离农田最远。
+pub fn select_target(
+    origin: Vec2,
+    range: f32,
+    priority: TargetPriority,
+    candidates: impl IntoIterator<Item = TargetCandidate>,
+) -> Option<Entity> {
+    candidates
+        .into_iter()
+        .map(|candidate| {
+            let distance = origin.distance(candidate.position);
+            let cost = candidate.cost.unwrap_or(u32::MAX) as f32;
+            let score = match priority {
+                TargetPriority::First => cost,
+                TargetPriority::Last => -cost,
+                TargetPriority::Strongest => -candidate.health,
+                TargetPriority::Closest => distance,
+            };
+            (candidate.entity, distance, score)
+        })
+        .filter(|(_, distance, _)| *distance <= range)
+        .min_by(|a, b| a.2.total_cmp(&b.2).then(a.1.total_cmp(&b.1)))
+        .map(|(entity, _, _)| entity)
+}
+
+/// 所有敌人所在的格子。
+fn enemy_positions<'a>(
+    level_map: &'a LevelMap,
+    enemy_query: &'a Query<&Transform, With<Enemy>>,
+) -> impl Iterator<Item = IVec2> + 'a {
+    enemy_query
+        .iter()
+        .filter_map(|transform| level_map.layout.world_to_grid(transform.translation.xy()))
+}
+
+fn init_tower_selection(mut commands: Commands) {
+    commands.init_resource::<TowerSelection>();
+}
+
+fn remove_tower_selection(mut commands: Commands) {
+    commands.remove_resource::<TowerSelection>();
+}
+
+const TOWER_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// 按数字键选择防御塔，再次按下同一个键取消选择。
+fn select_tower(
+    input: Res<ButtonInput<KeyCode>>,
+    tower_data: Res<TowerData>,
+    mut selection: ResMut<TowerSelection>,
+) {
+    for (index, key) in TOWER_KEYS.iter().enumerate() {
+        if input.just_pressed(*key) && index < tower_data.towers.len() {
+            selection.0 = (selection.0 != Some(index)).then_some(index);
+        }
+    }
+}
+
+fn clear_tower_selection(
+    mut commands: Commands,
+    mut selection: ResMut<TowerSelection>,
+    selected_query: Query<Entity, With<SelectedTower>>,
+) {
+    selection.0 = None;
+    for entity in &selected_query {
+        commands.entity(entity).remove::<SelectedTower>();
+    }
+}
+
+fn place_tower(
+    click: On<Pointer<Click>>,
+    mut commands: Commands,
+    tile_query: Query<&MapTile>,
+    enemy_query: Query<&Transform, With<Enemy>>,
+    level_map: Option<Res<LevelMap>>,
+    tower_data: Option<Res<TowerData>>,
+    selection: Option<Res<TowerSelection>>,
+    gold: Option<ResMut<Gold>>,
+) {
+    let (Some(level_map), Some(tower_data), Some(selection), Some(mut gold)) =
+        (level_map, tower_data, selection, gold)
+    else {
+        return;
+    };
+    if click.button != PointerButton::Primary {
+        return;
+    }
+    let Ok(tile) = tile_query.get(click.entity) else {
+        return;
+    };
+    let Some(def) = selection.0.and_then(|index| tower_data.towers.get(index)) else {
+        return;
+    };
+    if !can_place_tower(
+        &level_map,
+        tile.pos,
+        enemy_positions(&level_map, &enemy_query),
+    ) {
+        return;
+    }
+    match def.build(&mut gold) {
+        Ok(built) => {
+            commands.spawn(tower(def, built, tile.pos, &level_map));
+        }
+        Err(error) => info!("Can't build `{}`: {error}", def.id),
+    }
+}
+
+/// 左键点击已放置的防御塔将其选中。
+fn select_placed_tower(
+    click: On<Pointer<Click>>,
+    mut commands: Commands,
+    tower_query: Query<(), With<Tower>>,
+    selected_query: Query<Entity, With<SelectedTower>>,
+    selection: Option<ResMut<TowerSelection>>,
+) {
+    if click.button != PointerButton::Primary || !tower_query.contains(click.entity) {
+        return;
+    }
+    for entity in &selected_query {
+        commands.entity(entity).remove::<SelectedTower>();
+    }
+    commands.entity(click.entity).insert(SelectedTower);
+    if let Some(mut selection) = selection {
+        selection.0 = None;
+    }
+}
+
+fn upgrade_selected_tower(
+    tower_data: Res<TowerData>,
+    mut gold: ResMut<Gold>,
+    mut tower_query: Query<&mut Tower, With<SelectedTower>>,
+) {
+    for mut tower in &mut tower_query {
+        let Some(def) = tower_data.get(&tower.kind) else {
+            continue;
+        };
+        if let Err(error) = tower.upgrade(def, &mut gold) {
+            info!("Can't upgrade `{}`: {error}", def.id);
+        }
+    }
+}
+
+fn sell_selected_tower(
+    mut commands: Commands,
+    tower_data: Res<TowerData>,
+    mut gold: ResMut<Gold>,
+    tower_query: Query<(Entity, &Tower), With<SelectedTower>>,
+) {
+    for (entity, tower) in &tower_query {
+        gold.0 += tower_data.refund(tower);
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 防御塔的大小随等级变化，被选中的防御塔会高亮显示。
+fn update_tower_sprites(
+    tower_data: Res<TowerData>,
+    level_map: Res<LevelMap>,
+    mut tower_query: Query<(&Tower, Has<SelectedTower>, &mut Sprite)>,
+) {
+    for (tower, selected, mut sprite) in &mut tower_query {
+        let Some(def) = tower_data.get(&tower.kind) else {
+            continue;
+        };
+        let color = if selected {
+            def.color().lighter(0.2)
+        } else {
+            def.color()
+        };
+        let size = Vec2::splat(tower_size(&level_map, tower.tier));
+        if sprite.color != color || sprite.custom_size != Some(size) {
+            sprite.color = color;
+            sprite.custom_size = Some(size);
+        }
+    }
+}
+
+fn cycle_target_priority(click: On<Pointer<Click>>, mut tower_query: Query<&mut Tower>) {
+    if click.button != PointerButton::Secondary {
+        return;
+    }
+    if let Ok(mut tower) = tower_query.get_mut(click.entity) {
+        tower.priority = tower.priority.next();
+        info!("Tower priority: {:?}", tower.priority);
+    }
+}
+
+fn spawn_tower_preview(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Tower Preview"),
+        TowerPreview { pos: None },
+        Sprite::from_color(Color::NONE, Vec2::ZERO),
+        Transform::default(),
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        DespawnOnExit(ProtectTheLand::state()),
+    ));
+}
+
+fn move_tower_preview(
+    over: On<Pointer<Over>>,
+    tile_query: Query<&MapTile>,
+    mut preview_query: Query<&mut TowerPreview>,
+) {
+    let Ok(tile) = tile_query.get(over.entity) else {
+        return;
+    };
+    for mut preview in &mut preview_query {
+        preview.pos = Some(tile.pos);
+    }
+}
+
+fn update_tower_preview(
+    selection: Res<TowerSelection>,
+    tower_data: Res<TowerData>,
+    level_map: Res<LevelMap>,
+    gold: Res<Gold>,
+    enemy_query: Query<&Transform, With<Enemy>>,
+    mut preview_query: Query<
+        (&TowerPreview, &mut Sprite, &mut Transform, &mut Visibility),
+        Without<Enemy>,
+    >,
+) {
+    let def = selection.0.and_then(|index| tower_data.towers.get(index));
+    for (preview, mut sprite, mut transform, mut visibility) in &mut preview_query {
+        let (Some(def), Some(pos)) = (def, preview.pos) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        let valid = gold.0 >= def.tiers[0].cost
+            && can_place_tower(&level_map, pos, enemy_positions(&level_map, &enemy_query));
+        let color = if valid {
+            def.color().with_alpha(0.6)
+        } else {
+            Color::srgba(0.8, 0.1, 0.1, 0.6)
+        };
+        sprite.color = color;
+        sprite.custom_size = Some(Vec2::splat(tower_size(&level_map, 0)));
+        transform.translation = level_map.layout.grid_to_world(pos).extend(3.0);
+        *visibility = Visibility::Inherited;
+    }
+}
+
+fn tick_tower_cooldowns(time: Res<Time>, mut tower_query: Query<&mut Tower>) {
+    for mut tower in &mut tower_query {
+        tower.cooldown.tick(time.delta());
+    }
+}
+
+/// 冷却完成的防御塔选择范围内的目标并发射炮弹。
+fn fire_towers(
+    mut commands: Commands,
+    tower_data: Res<TowerData>,
+    level_map: Res<LevelMap>,
+    flow_field: Option<Res<FlowField>>,
+    mut tower_query: Query<(&mut Tower, &Transform)>,
+    enemy_query: Query<(Entity, &Transform, &Health), With<Enemy>>,
+    mut sound_writer: MessageWriter<PlaySound>,
+) {
+    for (mut tower, tower_transform) in &mut tower_query {
+        if !tower.cooldown.is_finished() {
+            continue;
+        }
+        let Some(def) = tower_data.get(&tower.kind) else {
+            continue;
+        };
+        let tier = &def.tiers[tower.tier];
+        let origin = tower_transform.translation.xy();
+        let candidates = enemy_query.iter().map(|(entity, transform, health)| {
+            let position = transform.translation.xy();
+            TargetCandidate {
+                entity,
+                position,
+                health: health.current,
+                cost: level_map
+                    .layout
+                    .world_to_grid(position)
+                    .and_then(|pos| flow_field.as_ref()?.cost(pos)),
+            }
+        });
+        let Some(target) = select_target(origin, tier.range, tower.priority, candidates) else {
+            continue;
+        };
+
+        commands.spawn(projectile(
+            origin,
+            target,
+            tier.damage,
+            def.damage_kind,
+            tier.projectile_speed,
+            def.color(),
+        ));
+        sound_writer.write(PlaySound(cues::TOWER_SHOOT));
+        tower.cooldown.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(index: u32) -> Entity {
+        Entity::from_raw_u32(index).unwrap()
+    }
+
+    /// 三个在攻击范围内的敌人：1 离农田最近，2 生命值最高，3 离防御塔最近。
+    fn candidates() -> [TargetCandidate; 3] {
+        [
+            TargetCandidate {
+                entity: entity(1),
+                position: Vec2::new(50.0, 0.0),
+                health: 10.0,
+                cost: Some(1),
+            },
+            TargetCandidate {
+                entity: entity(2),
+                position: Vec2::new(0.0, -40.0),
+                health: 30.0,
+                cost: Some(5),
+            },
+            TargetCandidate {
+                entity: entity(3),
+                position: Vec2::new(0.0, 20.0),
+                health: 20.0,
+                cost: Some(9),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_select_target_by_priority() {
+        let select = |priority| select_target(Vec2::ZERO, 100.0, priority, candidates());
+        assert_eq!(select(TargetPriority::First), Some(entity(1)));
+        assert_eq!(select(TargetPriority::Last), Some(entity(3)));
+        assert_eq!(select(TargetPriority::Strongest), Some(entity(2)));
+        assert_eq!(select(TargetPriority::Closest), Some(entity(3)));
+    }
+
+    #[test]
+    fn test_select_target_range() {
+        let [first, ..] = candidates();
+        // 恰好在攻击范围边界上的敌人可以被选中。
+        assert_eq!(
+            select_target(Vec2::ZERO, 50.0, TargetPriority::First, [first]),
+            Some(entity(1))
+        );
+        assert_eq!(
+            select_target(Vec2::ZERO, 49.9, TargetPriority::First, [first]),
+            None
+        );
+        // 范围外的敌人即使优先级更高也不会被选中。
+        assert_eq!(
+            select_target(Vec2::ZERO, 45.0, TargetPriority::First, candidates()),
+            Some(entity(2))
+        );
+        assert_eq!(
+            select_target(Vec2::ZERO, 100.0, TargetPriority::First, []),
+            None
+        );
+    }
+
+    #[test]
+    fn test_select_target_ties() {
+        let [mut near, mut far, _] = candidates();
+        near.cost = Some(3);
+        far.cost = Some(3);
+        near.position = Vec2::new(10.0, 0.0);
+        // 优先级相同时选择离防御塔最近的敌人。
+        assert_eq!(
+            select_target(Vec2::ZERO, 100.0, TargetPriority::First, [far, near]),
+            Some(entity(1))
+        );
+        // 不在流场中的敌人被视为离农田最远。
+        far.cost = None;
+        assert_eq!(
+            select_target(Vec2::ZERO, 100.0, TargetPriority::First, [far, near]),
+            Some(entity(1))
+        );
+        assert_eq!(
+            select_target(Vec2::ZERO, 100.0, TargetPriority::Last, [far, near]),
+            Some(entity(2))
+        );
+    }
+
+    fn tier(cost: u32) -> TowerTier {
+        TowerTier {
+            cost,
+            range: 100.0,
+            fire_interval: 1.0,
+            damage: 1.0,
+            projectile_speed: 100.0,
+        }
+    }
+
+    fn tower_data() -> TowerData {
+        TowerData {
+            sell_refund: 0.5,
+            towers: vec![TowerDef {
+                id: "arrow".to_string(),
+                name: "TOWER_ARROW".to_string(),
+                tiers: vec![tier(10), tier(15)],
+                damage_kind: DamageKind::default(),
+                color: (1.0, 1.0, 1.0),
+                priority: TargetPriority::Strongest,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_build_tower() {
+        let data = tower_data();
+        let def = &data.towers[0];
+
+        let mut gold = Gold(9);
+        assert_eq!(
+            def.build(&mut gold).err(),
+            Some(TowerError::NotEnoughGold { cost: 10, gold: 9 })
+        );
+        assert_eq!(gold.0, 9);
+
+        let mut gold = Gold(12);
+        let tower = def.build(&mut gold).unwrap();
+        assert_eq!(gold.0, 2);
+        assert_eq!(tower.kind, "arrow");
+        assert_eq!(tower.tier, 0);
+        assert_eq!(tower.invested, 10);
+        assert_eq!(tower.priority, TargetPriority::Strongest);
+    }
+
+    #[test]
+    fn test_upgrade_tower() {
+        let data = tower_data();
+        let def = &data.towers[0];
+        let mut gold = Gold(24);
+        let mut tower = def.build(&mut gold).unwrap();
+
+        assert_eq!(
+            tower.upgrade(def, &mut gold),
+            Err(TowerError::NotEnoughGold { cost: 15, gold: 14 })
+        );
+        assert_eq!((tower.tier, tower.invested, gold.0), (0, 10, 14));
+
+        gold.0 += 6;
+        assert_eq!(tower.upgrade(def, &mut gold), Ok(()));
+        assert_eq!((tower.tier, tower.invested, gold.0), (1, 25, 5));
+
+        assert_eq!(
+            tower.upgrade(def, &mut gold),
+            Err(TowerError::MaxTier("arrow".to_string()))
+        );
+        assert_eq!((tower.tier, tower.invested, gold.0), (1, 25, 5));
+    }
+
+    #[test]
+    fn test_sell_refund() {
+        let data = tower_data();
+        let def = &data.towers[0];
+        let mut gold = Gold(25);
+        let mut tower = def.build(&mut gold).unwrap();
+        assert_eq!(data.refund(&tower), 5);
+        tower.upgrade(def, &mut gold).unwrap();
+        // 返还建造和升级总花费的一半，向下取整。
+        assert_eq!(data.refund(&tower), 12);
+    }
+}
@@ -0,0 +1,479 @@
+//! 敌人波次。
+//!
+//! [`WaveScheduler`] 按照波次文件中的定义依次开始每一波：等待 `delay` 秒后，
+//! 每个敌人组按照 `interval` 的间隔在出生点生成 `count` 个敌人。
+//! 当一波的敌人全部生成并被消灭后，开始下一波的倒计时。最后一波被清除后本局游戏胜利。
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use rand::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    AppSystems, PausableSystems,
+    asset_tracking::{RonAsset, RonAssetLoader},
+    games::{
+        mini_game::{GameOutcome, GameOver, MiniGame},
+        protect_the_land::{
+            ProtectTheLand,
+            enemy::{Enemy, EnemyDef, enemy},
+            map::{LevelMap, Tile},
+        },
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<WaveScheduler>();
+    app.add_message::<WaveStarted>();
+    app.add_message::<WaveCleared>();
+
+    app.init_asset::<WaveData>();
+    app.register_asset_loader(RonAssetLoader::<WaveData>::default());
+
+    app.add_systems(OnExit(ProtectTheLand::state()), remove_wave_scheduler);
+    app.add_systems(
+        Update,
+        (
+            tick_wave_scheduler.in_set(AppSystems::TickTimers),
+            (update_wave_scheduler, log_wave_progress)
+                .chain()
+                .in_set(AppSystems::Update),
+        )
+            .run_if(in_state(ProtectTheLand::state()).and(resource_exists::<WaveScheduler>))
+            .in_set(PausableSystems),
+    );
+}
+
+/// 波次文件的内容。
+#[derive(Asset, TypePath, Deserialize, Clone, Default)]
+pub struct WaveData {
+    /// 敌人类型及其属性。
+    pub enemies: HashMap<String, EnemyDef>,
+    /// 按顺序进行的波次。
+    pub waves: Vec<WaveDef>,
+}
+
+/// 一波敌人。
+#[derive(Clone, Debug, Deserialize, Reflect)]
+pub struct WaveDef {
+    /// 上一波被清除（或游戏开始）后，到这一波开始前的等待时间（秒）。
+    pub delay: f32,
+    /// 这一波中同时开始生成的敌人组。
+    pub groups: Vec<SpawnGroupDef>,
+}
+
+/// 一组相同类型的敌人。
+#[derive(Clone, Debug, Deserialize, Reflect)]
+pub struct SpawnGroupDef {
+    /// 敌人类型。
+    pub enemy: String,
+    /// 敌人数量。
+    pub count: u32,
+    /// 两个敌人生成之间的间隔（秒）。
+    pub interval: f32,
+    /// 波次开始后，该组第一个敌人生成前的等待时间（秒）。
+    #[serde(default)]
+    pub delay: f32,
+}
+
+impl RonAsset for WaveData {
+    const EXTENSIONS: &'static [&'static str] = &["waves.ron"];
+
+    fn validate(&self) -> Result<(), String> {
+        if self.waves.is_empty() {
+            return Err("there are no waves".to_string());
+        }
+        for (index, wave) in self.waves.iter().enumerate() {
+            if wave.delay < 0.0 {
+                return Err(format!("wave {index} has a negative `delay`"));
+            }
+            for group in &wave.groups {
+                if !self.enemies.contains_key(&group.enemy) {
+                    return Err(format!(
+                        "wave {index} references unknown enemy `{}`",
+                        group.enemy
+                    ));
+                }
+                if group.interval < 0.0 || group.delay < 0.0 {
+                    return Err(format!("wave {index} has a negative `interval` or `delay`"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 一波敌人开始生成。
+#[derive(Message, Clone, Debug)]
+pub struct WaveStarted {
+    /// 波次编号（从 1 开始）。
+    pub wave: usize,
+    /// 总波次数。
+    pub total: usize,
+}
+
+/// 一波敌人全部被消灭。
+#[derive(Message, Clone, Debug)]
+pub struct WaveCleared {
+    /// 波次编号（从 1 开始）。
+    pub wave: usize,
+    /// 总波次数。
+    pub total: usize,
+}
+
+/// 控制波次的进行。仅在游戏运行时存在。
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct WaveScheduler {
+    #[reflect(ignore)]
+    data: WaveData,
+    /// 下一波（或当前波）的索引。
+    index: usize,
+    state: WaveState,
+}
+
+#[derive(Reflect, Debug)]
+enum WaveState {
+    /// 等待下一波开始。
+    Countdown(Timer),
+    /// 正在生成敌人。
+    Spawning(Vec<GroupSpawner>),
+    /// 所有敌人已生成，等待它们被消灭。
+    Fighting,
+    /// 所有波次都已结束。
+    Finished,
+}
+
+/// [`WaveScheduler::update`] 中发生的波次变化，波次编号从 1 开始。
+#[derive(Debug, PartialEq, Eq)]
+enum WaveProgress {
+    Started(usize),
+    Cleared(usize),
+}
+
+/// 一个正在生成的敌人组。
+#[derive(Reflect, Debug)]
+struct GroupSpawner {
+    enemy: String,
+    remaining: u32,
+    interval: f32,
+    timer: Timer,
+}
+
+impl WaveScheduler {
+    pub fn new(data: WaveData) -> Self {
+        let state = WaveState::Countdown(Timer::from_seconds(data.waves[0].delay, TimerMode::Once));
+        Self {
+            data,
+            index: 0,
+            state,
+        }
+    }
+
+    /// 总波次数。
+    pub fn total_waves(&self) -> usize {
+        self.data.waves.len()
+    }
+
+    /// 当前（或即将开始的）波次编号，从 1 开始。
+    pub fn wave_number(&self) -> usize {
+        (self.index + 1).min(self.total_waves())
+    }
+
+    /// 下一波开始前剩余的时间（秒）。不在倒计时中时返回 `None`。
+    pub fn countdown(&self) -> Option<f32> {
+        match &self.state {
+            WaveState::Countdown(timer) => Some(timer.remaining_secs()),
+            _ => None,
+        }
+    }
+
+    /// 所有波次是否都已结束。
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state, WaveState::Finished)
+    }
+
+    fn tick(&mut self, delta: std::time::Duration) {
+        match &mut self.state {
+            WaveState::Countdown(timer) => {
+                timer.tick(delta);
+            }
+            WaveState::Spawning(groups) => {
+                for group in groups {
+                    group.timer.tick(delta);
+                }
+            }
+            WaveState::Fighting | WaveState::Finished => {}
+        }
+    }
+
+    /// 推进波次状态。`enemies_alive` 表示场上是否还有敌人；
+    /// `spawn` 生成一个敌人，没有可用的出生点时返回 `false`，该敌人会在之后重试。
+    fn update(
+        &mut self,
+        enemies_alive: bool,
+        mut spawn: impl FnMut(&str, &EnemyDef) -> bool,
+    ) -> Option<WaveProgress> {
+        match &mut self.state {
+            WaveState::Countdown(timer) => {
+                if !timer.is_finished() {
+                    return None;
+                }
+                let wave = &self.data.waves[self.index];
+                let groups = wave
+                    .groups
+                    .iter()
+                    .map(|group| GroupSpawner {
+                        enemy: group.enemy.clone(),
+                        remaining: group.count,
+                        interval: group.interval,
+                        timer: Timer::from_seconds(group.delay, TimerMode::Once),
+                    })
+                    .collect();
+                self.state = WaveState::Spawning(groups);
+                Some(WaveProgress::Started(self.index + 1))
+            }
+            WaveState::Spawning(groups) => {
+                for group in groups.iter_mut() {
+                    if group.remaining == 0 || !group.timer.is_finished() {
+                        continue;
+                    }
+                    let Some(def) = self.data.enemies.get(&group.enemy) else {
+                        continue;
+                    };
+                    if !spawn(&group.enemy, def) {
+                        continue;
+                    }
+                    group.remaining -= 1;
+                    group.timer = Timer::from_seconds(group.interval, TimerMode::Once);
+                }
+                if groups.iter().all(|group| group.remaining == 0) {
+                    self.state = WaveState::Fighting;
+                }
+                None
+            }
+            WaveState::Fighting => {
+                if enemies_alive {
+                    return None;
+                }
+                let wave = self.index + 1;
+                self.index += 1;
+                self.state = match self.data.waves.get(self.index) {
+                    Some(wave) => {
+                        WaveState::Countdown(Timer::from_seconds(wave.delay, TimerMode::Once))
+                    }
+                    None => WaveState::Finished,
+                };
+                Some(WaveProgress::Cleared(wave))
+            }
+            WaveState::Finished => None,
+        }
+    }
+}
+
+fn tick_wave_scheduler(time: Res<Time>, mut scheduler: ResMut<WaveScheduler>) {
+    scheduler.tick(time.delta());
+}
+
+fn update_wave_scheduler(
+    mut commands: Commands,
+    mut scheduler: ResMut<WaveScheduler>,
+    level_map: Res<LevelMap>,
+    enemy_query: Query<(), With<Enemy>>,
+    mut wave_started: MessageWriter<WaveStarted>,
+    mut wave_cleared: MessageWriter<WaveCleared>,
+    mut game_over: MessageWriter<GameOver>,
+) {
+    let spawn_points = level_map.positions_of(Tile::Spawn).collect::<Vec<_>>();
+    let rng = &mut rand::thread_rng();
+    let progress = scheduler.update(!enemy_query.is_empty(), |name, def| {
+        let Some(spawn_point) = spawn_points.choose(rng) else {
+            return false;
+        };
+        let position = level_map.layout.grid_to_world(*spawn_point);
+        commands.spawn(enemy(name, def, position));
+        true
+    });
+    let total = scheduler.total_waves();
+    match progress {
+        Some(WaveProgress::Started(wave)) => {
+            wave_started.write(WaveStarted { wave, total });
+        }
+        Some(WaveProgress::Cleared(wave)) => {
+            wave_cleared.write(WaveCleared { wave, total });
+            if scheduler.is_finished() {
+                game_over.write(GameOver {
+                    outcome: GameOutcome::Victory,
+                });
+            }
+        }
+        None => {}
+    }
+}
+
+fn log_wave_progress(
+    mut wave_started: MessageReader<WaveStarted>,
+    mut wave_cleared: MessageReader<WaveCleared>,
+) {
+    for WaveStarted { wave, total } in wave_started.read() {
+        info!("Wave {wave}/{total} started");
+    }
+    for WaveCleared { wave, total } in wave_cleared.read() {
+        info!("Wave {wave}/{total} cleared");
+    }
+}
+
+fn remove_wave_scheduler(mut commands: Commands) {
+    commands.remove_resource::<WaveScheduler>();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    const WAVES: &str = r#"(
+        enemies: {
+            "beetle": (
+                health: 30.0,
+                damage: 5.0,
+                gold: 5,
+                speed: 60.0,
+                size: 20.0,
+                color: (0.3, 0.2, 0.45),
+            ),
+        },
+        waves: [
+            (
+                delay: 2.0,
+                groups: [
+                    (enemy: "beetle", count: 2, interval: 1.0),
+                    (enemy: "beetle", count: 0, interval: 1.0),
+                ],
+            ),
+            (delay: 1.0, groups: []),
+        ],
+    )"#;
+
+    fn wave_data() -> WaveData {
+        ron::from_str(WAVES).unwrap()
+    }
+
+    #[test]
+    fn test_wave_scheduler() {
+        let mut scheduler = WaveScheduler::new(wave_data());
+        let mut spawned = 0;
+        let mut update = |scheduler: &mut WaveScheduler, secs: f32, enemies_alive: bool| {
+            scheduler.tick(Duration::from_secs_f32(secs));
+            scheduler.update(enemies_alive, |name, _| {
+                assert_eq!(name, "beetle");
+                spawned += 1;
+                true
+            })
+        };
+
+        // 第一波倒计时。
+        assert_eq!(scheduler.total_waves(), 2);
+        assert_eq!(scheduler.wave_number(), 1);
+        assert_eq!(update(&mut scheduler, 1.0, false), None);
+        assert_eq!(scheduler.countdown(), Some(1.0));
+        assert_eq!(
+            update(&mut scheduler, 1.0, false),
+            Some(WaveProgress::Started(1))
+        );
+        assert_eq!(scheduler.countdown(), None);
+
+        // 按间隔生成敌人，`count: 0` 的组不生成敌人。
+        assert_eq!(update(&mut scheduler, 0.0, false), None);
+        assert!(matches!(scheduler.state, WaveState::Spawning(_)));
+        assert_eq!(update(&mut scheduler, 0.5, true), None);
+        assert!(matches!(scheduler.state, WaveState::Spawning(_)));
+        assert_eq!(update(&mut scheduler, 0.5, true), None);
+        assert!(matches!(scheduler.state, WaveState::Fighting));
+
+        // 敌人全部被消灭后开始下一波的倒计时。
+        assert_eq!(update(&mut scheduler, 1.0, true), None);
+        assert_eq!(
+            update(&mut scheduler, 1.0, false),
+            Some(WaveProgress::Cleared(1))
+        );
+        assert_eq!(scheduler.wave_number(), 2);
+        assert_eq!(scheduler.countdown(), Some(1.0));
+
+        // 没有敌人组的一波立即被清除，最后一波被清除后结束。
+        assert_eq!(
+            update(&mut scheduler, 1.0, false),
+            Some(WaveProgress::Started(2))
+        );
+        assert_eq!(update(&mut scheduler, 0.0, false), None);
+        assert!(matches!(scheduler.state, WaveState::Fighting));
+        assert!(!scheduler.is_finished());
+        assert_eq!(
+            update(&mut scheduler, 0.0, false),
+            Some(WaveProgress::Cleared(2))
+        );
+        assert!(scheduler.is_finished());
+        assert_eq!(scheduler.wave_number(), 2);
+        assert_eq!(update(&mut scheduler, 1.0, false), None);
+        assert_eq!(spawned, 2);
+    }
+
+    #[test]
+    fn test_wave_scheduler_missing_spawn_point() {
+        let mut scheduler = WaveScheduler::new(wave_data());
+        scheduler.tick(Duration::from_secs(2));
+        assert_eq!(
+            scheduler.update(false, |_, _| true),
+            Some(WaveProgress::Started(1))
+        );
+        // 没有出生点时不计入已生成的敌人。
+        scheduler.tick(Duration::ZERO);
+        assert_eq!(scheduler.update(false, |_, _| false), None);
+        assert!(
+            matches!(&scheduler.state, WaveState::Spawning(groups) if groups[0].remaining == 2)
+        );
+    }
+
+    #[test]
+    fn test_validate_waves() {
+        assert_eq!(wave_data().validate(), Ok(()));
+        let level: WaveData = ron::from_str(include_str!(
+            "../../../assets/levels/protect_the_land.waves.ron"
+        ))
+        .unwrap();
+        assert_eq!(level.validate(), Ok(()));
+
+        let mut data = wave_data();
+        data.waves.clear();
+        assert!(data.validate().is_err());
+
+        let mut data = wave_data();
+        data.waves[1].groups.push(SpawnGroupDef {
+            enemy: "dragon".to_string(),
+            count: 1,
+            interval: 1.0,
+            delay: 0.0,
+        });
+        assert_eq!(
+            data.validate(),
+            Err("wave 1 references unknown enemy `dragon`".to_string())
+        );
+
+        let mut data = wave_data();
+        data.waves[1].delay = -1.0;
+        assert_eq!(
+            data.validate(),
+            Err("wave 1 has a negative `delay`".to_string())
+        );
+
+        let mut data = wave_data();
+        data.waves[0].groups[0].interval = -1.0;
+        assert!(data.validate().is_err());
+        let mut data = wave_data();
+        data.waves[0].groups[1].delay = -0.5;
+        assert_eq!(
+            data.validate(),
+            Err("wave 0 has a negative `interval` or `delay`".to_string())
+        );
+    }
+}
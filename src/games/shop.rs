@@ -0,0 +1,189 @@
+//! 商店的价格表和交易。
+//!
+//! 价格表从 `assets/shop.ron` 加载，每个条目可以只买入、只卖出或两者皆可。
+//! 交易使用 [`Gold`] 和玩家的 [`Inventory`]，界面见 `menus::shop`。
+
+use bevy::{platform::collections::HashSet, prelude::*};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    asset_tracking::{LoadResource, RonAsset, RonAssetLoader},
+    games::{
+        gold::Gold,
+        inventory::{Inventory, InventoryError, ItemData},
+    },
+};
+
+/// 价格表文件的路径。
+const SHOP_PATH: &str = "shop.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ShopData>();
+    app.register_asset_loader(RonAssetLoader::<ShopData>::default());
+    app.load_resource_from_path::<ShopData>(SHOP_PATH);
+    app.add_systems(
+        Update,
+        check_shop_references
+            .run_if(resource_exists::<ItemData>.and(resource_exists_and_changed::<ShopData>)),
+    );
+}
+
+/// 商店的价格表。
+#[derive(Resource, Asset, Clone, Reflect, Deserialize)]
+#[reflect(Resource)]
+pub struct ShopData {
+    /// 按显示顺序排列的商品。
+    pub entries: Vec<ShopEntry>,
+}
+
+/// 一种商品。
+#[derive(Clone, Debug, Reflect, Deserialize)]
+pub struct ShopEntry {
+    /// 物品标识，对应 [`ItemDef::id`](crate::games::inventory::ItemDef::id)。
+    pub item: String,
+    /// 买入一个的价格，`None` 表示商店不出售。
+    #[serde(default)]
+    pub buy: Option<u32>,
+    /// 卖出一个获得的金币，`None` 表示商店不收购。
+    #[serde(default)]
+    pub sell: Option<u32>,
+}
+
+impl RonAsset for ShopData {
+    const EXTENSIONS: &'static [&'static str] = &["shop.ron"];
+
+    fn validate(&self) -> Result<(), String> {
+        let mut ids = HashSet::new();
+        for entry in &self.entries {
+            if !ids.insert(entry.item.as_str()) {
+                return Err(format!("duplicate shop entry `{}`", entry.item));
+            }
+            if entry.buy.is_none() && entry.sell.is_none() {
+                return Err(format!(
+                    "shop entry `{}` can be neither bought nor sold",
+                    entry.item
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 交易失败的原因。
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ShopError {
+    #[error("the shop does not sell `{0}`")]
+    NotForSale(String),
+    #[error("the shop does not buy `{0}`")]
+    NotWanted(String),
+    #[error("not enough gold: costs {price}, have {gold}")]
+    NotEnoughGold { price: u32, gold: u32 },
+    #[error("there is no room for `{0}`")]
+    InventoryFull(String),
+    #[error(transparent)]
+    Inventory(#[from] InventoryError),
+}
+
+impl ShopData {
+    /// 返回该物品的商品条目。
+    pub fn get(&self, item: &str) -> Option<&ShopEntry> {
+        self.entries.iter().find(|entry| entry.item == item)
+    }
+
+    /// 花费金币买入一个物品放进背包。失败时金币和背包都不变。
+    pub fn buy(
+        &self,
+        items: &ItemData,
+        item: &str,
+        gold: &mut Gold,
+        inventory: &mut Inventory,
+    ) -> Result<(), ShopError> {
+        let price = self
+            .get(item)
+            .and_then(|entry| entry.buy)
+            .ok_or_else(|| ShopError::NotForSale(item.to_string()))?;
+        if inventory.room_for(items, item) == 0 {
+            return Err(ShopError::InventoryFull(item.to_string()));
+        }
+        if !gold.try_spend(price) {
+            return Err(ShopError::NotEnoughGold {
+                price,
+                gold: gold.0,
+            });
+        }
+        inventory.add(items, item, 1);
+        Ok(())
+    }
+
+    /// 从背包中卖出一个物品换取金币。失败时金币和背包都不变。
+    pub fn sell(
+        &self,
+        item: &str,
+        gold: &mut Gold,
+        inventory: &mut Inventory,
+    ) -> Result<(), ShopError> {
+        let price = self
+            .get(item)
+            .and_then(|entry| entry.sell)
+            .ok_or_else(|| ShopError::NotWanted(item.to_string()))?;
+        inventory.remove(item, 1)?;
+        gold.0 += price;
+        Ok(())
+    }
+}
+
+/// 价格表引用了不存在的物品时发出警告。
+fn check_shop_references(shop: Res<ShopData>, items: Res<ItemData>) {
+    for entry in &shop.entries {
+        if items.get(&entry.item).is_none() {
+            warn!("Shop entry references unknown item `{}`", entry.item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::inventory::ItemDef;
+
+    #[test]
+    fn test_buy_and_sell() {
+        let items = ItemData {
+            items: vec![ItemDef {
+                id: "seeds".to_string(),
+                name: String::new(),
+                icon: String::new(),
+                max_stack: 2,
+            }],
+        };
+        let shop = ShopData {
+            entries: vec![ShopEntry {
+                item: "seeds".to_string(),
+                buy: Some(5),
+                sell: Some(2),
+            }],
+        };
+        let mut gold = Gold(12);
+        let mut inventory = Inventory::new(1);
+
+        assert_eq!(shop.buy(&items, "seeds", &mut gold, &mut inventory), Ok(()));
+        assert_eq!(shop.buy(&items, "seeds", &mut gold, &mut inventory), Ok(()));
+        assert_eq!(
+            shop.buy(&items, "seeds", &mut gold, &mut inventory),
+            Err(ShopError::InventoryFull("seeds".to_string()))
+        );
+        assert_eq!(gold.0, 2);
+
+        assert_eq!(shop.sell("seeds", &mut gold, &mut inventory), Ok(()));
+        assert_eq!(
+            shop.buy(&items, "seeds", &mut gold, &mut inventory),
+            Err(ShopError::NotEnoughGold { price: 5, gold: 4 })
+        );
+        assert_eq!(inventory.count("seeds"), 1);
+        assert_eq!(
+            shop.sell("apple", &mut gold, &mut inventory),
+            Err(ShopError::NotWanted("apple".to_string()))
+        );
+    }
+}
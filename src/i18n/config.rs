@@ -1,6 +1,7 @@
-//!多语言文本配置
-
-use crate::i18n::LanguageRes;
+//! 多语言文本的 Key。
+//!
+//! 各语言的文本在 `assets/i18n/<语言>.locale.ron` 中，见 [`locale`](super::locale)。
+//! 只在数据文件中引用的 Key（例如物品和防御塔的名称）不需要在这里定义。
 
 /// 主菜单开始游戏
 pub const MAIN_PLAY: &str = "MAIN_PLAY";
@@ -10,6 +11,8 @@ pub const MAIN_SETTINGS: &str = "MAIN_SETTINGS";
 pub const MAIN_CREDITS: &str = "MAIN_CREDITS";
 /// 主菜单退出
 pub const MAIN_EXIT: &str = "MAIN_EXIT";
+/// 语言选择界面标题
+pub const LANGUAGE_TITLE: &str = "LANGUAGE_TITLE";
 /// 暂停界面标题
 pub const PAUSE_GAME_TITLE: &str = "PAUSE_GAME_TITLE";
 /// 暂停界面继续游戏
@@ -20,70 +23,94 @@ pub const PAUSE_QUIT_TO_TITLE: &str = "PAUSE_QUIT_TO_TITLE";
 pub const SETTINGS_TITLE: &str = "SETTINGS_TITLE";
 /// 设置界面主音量
 pub const SETTINGS_MASTER_VOLUME: &str = "MASTER_VOLUME";
-/// 设置界面当前音量
-pub const SETTINGS_CURRENT_VOLUME: &str = "CURRENT_VOLUME";
+/// 设置界面各类声音的音量
+pub const SETTINGS_MUSIC_VOLUME: &str = "SETTINGS_MUSIC_VOLUME";
+pub const SETTINGS_SFX_VOLUME: &str = "SETTINGS_SFX_VOLUME";
+pub const SETTINGS_UI_VOLUME: &str = "SETTINGS_UI_VOLUME";
+/// 设置界面的音量百分比，参数 `percent`
+pub const SETTINGS_VOLUME_PERCENT: &str = "SETTINGS_VOLUME_PERCENT";
+/// 设置界面全屏
+pub const SETTINGS_FULLSCREEN: &str = "SETTINGS_FULLSCREEN";
+/// 设置界面开关的状态
+pub const SETTINGS_ON: &str = "SETTINGS_ON";
+pub const SETTINGS_OFF: &str = "SETTINGS_OFF";
+/// 设置界面切换开关
+pub const SETTINGS_TOGGLE: &str = "SETTINGS_TOGGLE";
 /// 选择游戏界面标题
 pub const SELECT_GAME_TITLE: &str = "SELECT_GAME_TITLE";
+/// 结算界面胜利标题
+pub const RESULTS_VICTORY: &str = "RESULTS_VICTORY";
+/// 结算界面失败标题
+pub const RESULTS_DEFEAT: &str = "RESULTS_DEFEAT";
+/// 结算界面游戏时间
+pub const RESULTS_TIME: &str = "RESULTS_TIME";
+/// 结算界面再玩一次
+pub const RESULTS_RETRY: &str = "RESULTS_RETRY";
+/// 结算界面回到选择游戏
+pub const RESULTS_SELECT_GAME: &str = "RESULTS_SELECT_GAME";
+/// “保护那块地!!”坚持的波数
+pub const PROTECT_THE_LAND_WAVES_SURVIVED: &str = "PROTECT_THE_LAND_WAVES_SURVIVED";
+/// “保护那块地!!”消灭的敌人数
+pub const PROTECT_THE_LAND_ENEMIES_KILLED: &str = "PROTECT_THE_LAND_ENEMIES_KILLED";
+/// “保护那块地!!”当前金币，参数 `gold`
+pub const PROTECT_THE_LAND_GOLD: &str = "PROTECT_THE_LAND_GOLD";
+/// “保护那块地!!”获得的金币
+pub const PROTECT_THE_LAND_GOLD_EARNED: &str = "PROTECT_THE_LAND_GOLD_EARNED";
+/// “种个锤子地”收获的作物数
+pub const FARMING_CROPS_HARVESTED: &str = "FARMING_CROPS_HARVESTED";
+/// “保护那块地!!”农田的生命值，参数 `health` 和 `max`
+pub const PROTECT_THE_LAND_FARMLAND: &str = "PROTECT_THE_LAND_FARMLAND";
+/// “保护那块地!!”当前波次，参数 `n` 和 `total`
+pub const PROTECT_THE_LAND_WAVE: &str = "PROTECT_THE_LAND_WAVE";
+/// “保护那块地!!”下一波倒计时，参数 `seconds`
+pub const PROTECT_THE_LAND_NEXT_WAVE: &str = "PROTECT_THE_LAND_NEXT_WAVE";
+/// “保护那块地!!”建造栏的格子，参数 `n`（快捷键）、`tower`（防御塔名称）和 `cost`
+pub const PROTECT_THE_LAND_BUILD_SLOT: &str = "PROTECT_THE_LAND_BUILD_SLOT";
+/// “种个锤子地”经过的天数
+pub const FARMING_DAYS: &str = "FARMING_DAYS";
+// 游戏内日历
+/// 日期和时间，参数 `day`、`season`（季节名称）和 `time`
+pub const CLOCK_DAY: &str = "CLOCK_DAY";
+pub const SEASON_SPRING: &str = "SEASON_SPRING";
+pub const SEASON_SUMMER: &str = "SEASON_SUMMER";
+pub const SEASON_AUTUMN: &str = "SEASON_AUTUMN";
+pub const SEASON_WINTER: &str = "SEASON_WINTER";
+/// “种个锤子地”当前选择的工具，参数 `tool`（工具名称）
+pub const FARMING_TOOL: &str = "FARMING_TOOL";
+// 玩家工具的名称
+pub const TOOL_HOE: &str = "TOOL_HOE";
+pub const TOOL_WATERING_CAN: &str = "TOOL_WATERING_CAN";
+pub const TOOL_SEED_BAG: &str = "TOOL_SEED_BAG";
+pub const TOOL_SICKLE: &str = "TOOL_SICKLE";
+
+// 商店
+pub const SHOP_TITLE: &str = "SHOP_TITLE";
+pub const SHOP_ITEM: &str = "SHOP_ITEM";
+pub const SHOP_BUY_PRICE: &str = "SHOP_BUY_PRICE";
+pub const SHOP_SELL_PRICE: &str = "SHOP_SELL_PRICE";
+pub const SHOP_OWNED: &str = "SHOP_OWNED";
+pub const SHOP_BUY: &str = "SHOP_BUY";
+pub const SHOP_SELL: &str = "SHOP_SELL";
+
+// 存档
+pub const SAVE_TITLE: &str = "SAVE_TITLE";
+pub const LOAD_TITLE: &str = "LOAD_TITLE";
+/// 空存档位，参数 `n`（从 1 开始）和 `description`（[`SAVE_EMPTY`] 的文本）
+pub const SAVE_SLOT: &str = "SAVE_SLOT";
+/// 有存档的存档位，参数 `n`、`game`（游戏名称）、`time`（保存时间）和 `playtime`（游戏时间）
+pub const SAVE_SLOT_SAVED: &str = "SAVE_SLOT_SAVED";
+pub const SAVE_EMPTY: &str = "SAVE_EMPTY";
+pub const SAVE_SAVE: &str = "SAVE_SAVE";
+pub const SAVE_LOAD: &str = "SAVE_LOAD";
 
 // 全局文本
+/// 加载界面。第一次启动时语言文件可能还没有加载，使用 [`BUILTIN_TEXTS`] 中的文本
+pub const LOADING: &str = "LOADING";
 /// 返回
 pub const BACK: &str = "BACK";
+/// 当前金币，参数 `gold`
+pub const GOLD: &str = "GOLD";
 pub const GAME_TITLE: &str = "GAME_TITLE";
-pub const GAMES_NAME_1: &str = "GAMES_NAME_1";
-pub const GAMES_DESCRIPTION_1: &str = "GAMES_DESCRIPTION_1";
-
-pub(crate) fn init_language_res() -> LanguageRes {
-    let mut language_res = LanguageRes::default();
-    language_res.zh_cn(MAIN_PLAY, "开始游戏");
-    language_res.en_us(MAIN_PLAY, "PLAY");
-
-    language_res.zh_cn(MAIN_SETTINGS, "设置");
-    language_res.en_us(MAIN_SETTINGS, "SETTINGS");
-
-    language_res.zh_cn(MAIN_CREDITS, "关于");
-    language_res.en_us(MAIN_CREDITS, "CREDITS");
-
-    language_res.zh_cn(MAIN_EXIT, "退出");
-    language_res.en_us(MAIN_EXIT, "EXIT");
-
-    language_res.zh_cn(PAUSE_GAME_TITLE, "游戏已暂停");
-    language_res.en_us(PAUSE_GAME_TITLE, "Game Paused");
-
-    language_res.zh_cn(PAUSE_CONTINUE, "继续游戏");
-    language_res.en_us(PAUSE_CONTINUE, "CONTINUE");
-
-    language_res.zh_cn(PAUSE_QUIT_TO_TITLE, "回到主菜单");
-    language_res.en_us(PAUSE_QUIT_TO_TITLE, "QUIT TO TITLE");
-
-    language_res.zh_cn(SETTINGS_TITLE, "设置");
-    language_res.en_us(SETTINGS_TITLE, "SETTINGS");
-
-    language_res.zh_cn(SETTINGS_MASTER_VOLUME, "主音量");
-    language_res.en_us(SETTINGS_MASTER_VOLUME, "Master Volume");
-
-    language_res.zh_cn(SETTINGS_CURRENT_VOLUME, "当前音量");
-    language_res.en_us(SETTINGS_CURRENT_VOLUME, "Current Volume");
-
-    language_res.zh_cn(BACK, "返回");
-    language_res.en_us(BACK, "BACK");
-
-    language_res.zh_cn(GAME_TITLE, "种个锤子地");
-    language_res.en_us(GAME_TITLE, "WTF Farming");
-
-    language_res.zh_cn(GAMES_NAME_1, "保护那块地!!");
-    language_res.en_us(GAMES_NAME_1, "Protect the Land!!");
-
-    language_res.zh_cn(
-        GAMES_DESCRIPTION_1,
-        "一个简单的塔防游戏，保护你的土地免受敌人的侵袭。",
-    );
-    language_res.en_us(
-        GAMES_DESCRIPTION_1,
-        "A simple tower defense game to protect your land from enemies.",
-    );
-
-    language_res.zh_cn(SELECT_GAME_TITLE, "选择游戏");
-    language_res.en_us(SELECT_GAME_TITLE, "Select Game");
 
-    language_res
-}
+/// 语言文件加载之前就可能显示的文本（默认语言），其他时候使用语言文件中的文本。
+pub const BUILTIN_TEXTS: &[(&str, &str)] = &[(LOADING, "Loading...")];
@@ -0,0 +1,331 @@
+//! 带参数的文本：命名占位符、复数和数字格式。
+//!
+//! 文本中可以使用以下语法（与 ICU MessageFormat 的常用部分相同）：
+//!
+//! - `{name}`：替换为参数 `name` 的值，数字按当前语言的习惯格式化，例如英语中的 `1,234.5`。
+//! - `{name, plural, one {# seed} other {# seeds}}`：根据数字参数 `name` 的复数类别选择一个分支，
+//!   分支中的 `#` 替换为格式化后的数字。分支也可以用 `=0` 这样的写法匹配一个确定的值，
+//!   找不到匹配的分支时使用 `other`。
+//! - `{{` 和 `}}`：字面的花括号。
+//!
+//! 复数类别遵循 CLDR 规则，只实现了常见语言的整数规则，其他语言按英语处理。
+
+use std::{borrow::Cow, fmt::Write};
+
+use bevy::reflect::Reflect;
+
+use crate::i18n::LanguageId;
+
+/// 文本的参数值。
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub enum TextArg {
+    Int(i64),
+    /// 小数和要显示的小数位数。
+    Float(f64, usize),
+    Text(String),
+    /// 另一个文本 Key，[`LanguageRes::get_args`](super::LanguageRes::get_args) 把它替换为当前语言中的文本。
+    Key(Cow<'static, str>),
+}
+
+macro_rules! impl_int_arg {
+    ($($ty:ty),*) => {
+        $(impl From<$ty> for TextArg {
+            fn from(value: $ty) -> Self {
+                Self::Int(value as i64)
+            }
+        })*
+    };
+}
+
+impl_int_arg!(i32, i64, u32, u64, usize);
+
+impl From<&str> for TextArg {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for TextArg {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+/// CLDR 复数类别。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PluralCategory {
+    One,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    fn name(self) -> &'static str {
+        match self {
+            Self::One => "one",
+            Self::Few => "few",
+            Self::Many => "many",
+            Self::Other => "other",
+        }
+    }
+
+    /// 整数 `n` 在 `language` 中的复数类别。
+    pub fn of(language: &LanguageId, n: u64) -> Self {
+        let (ones, tens) = (n % 10, n % 100);
+        match primary_subtag(language) {
+            "zh" | "ja" | "ko" | "vi" | "th" | "id" | "ms" => Self::Other,
+            "fr" => {
+                if n <= 1 {
+                    Self::One
+                } else {
+                    Self::Other
+                }
+            }
+            "ru" | "uk" => {
+                if ones == 1 && tens != 11 {
+                    Self::One
+                } else if (2..=4).contains(&ones) && !(12..=14).contains(&tens) {
+                    Self::Few
+                } else {
+                    Self::Many
+                }
+            }
+            "pl" => {
+                if n == 1 {
+                    Self::One
+                } else if (2..=4).contains(&ones) && !(12..=14).contains(&tens) {
+                    Self::Few
+                } else {
+                    Self::Many
+                }
+            }
+            "cs" | "sk" => match n {
+                1 => Self::One,
+                2..=4 => Self::Few,
+                _ => Self::Other,
+            },
+            _ => {
+                if n == 1 {
+                    Self::One
+                } else {
+                    Self::Other
+                }
+            }
+        }
+    }
+}
+
+fn primary_subtag(language: &LanguageId) -> &str {
+    language.0.split('-').next().unwrap_or_default()
+}
+
+/// 格式化一个数字参数：整数部分分组，使用当前语言的小数点。
+pub fn format_number(language: &LanguageId, arg: &TextArg) -> String {
+    let (decimal, group) = match primary_subtag(language) {
+        "de" | "es" | "it" | "pt" | "nl" | "id" | "tr" | "da" => (',', Some('.')),
+        "fr" | "ru" | "uk" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" => (',', Some(' ')),
+        _ => ('.', Some(',')),
+    };
+    let digits = match arg {
+        TextArg::Int(value) => value.to_string(),
+        TextArg::Float(value, precision) => format!("{value:.precision$}"),
+        TextArg::Text(text) => return text.clone(),
+        TextArg::Key(key) => return key.to_string(),
+    };
+    let (sign, digits) = match digits.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", digits.as_str()),
+    };
+    let (integer, fraction) = match digits.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (digits, None),
+    };
+
+    let mut result = sign.to_string();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0
+            && (integer.len() - i) % 3 == 0
+            && let Some(group) = group
+        {
+            result.push(group);
+        }
+        result.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        result.push(decimal);
+        result.push_str(fraction);
+    }
+    result
+}
+
+/// 用 `args` 格式化 `pattern`。缺少的参数和无法解析的部分按原样保留，方便发现翻译中的错误。
+pub fn format(pattern: &str, language: &LanguageId, args: &[(&str, TextArg)]) -> String {
+    let mut result = String::new();
+    format_into(&mut result, pattern, language, args, None);
+    result
+}
+
+/// 把 `pattern` 格式化到 `out`。`hash` 是复数分支中 `#` 代表的数字。
+fn format_into(
+    out: &mut String,
+    pattern: &str,
+    language: &LanguageId,
+    args: &[(&str, TextArg)],
+    hash: Option<&str>,
+) {
+    let mut rest = pattern;
+    while let Some(index) = rest.find(['{', '}', '#']) {
+        out.push_str(&rest[..index]);
+        let c = rest.as_bytes()[index];
+        rest = &rest[index + 1..];
+        match c {
+            b'#' => out.push_str(hash.unwrap_or("#")),
+            b'}' => {
+                // `}}` 是字面的 `}`，单独的 `}` 也按原样输出。
+                rest = rest.strip_prefix('}').unwrap_or(rest);
+                out.push('}');
+            }
+            _ if rest.starts_with('{') => {
+                rest = &rest[1..];
+                out.push('{');
+            }
+            _ => {
+                let Some(end) = matching_brace(rest) else {
+                    out.push('{');
+                    continue;
+                };
+                format_placeholder(out, &rest[..end], language, args);
+                rest = &rest[end + 1..];
+            }
+        }
+    }
+    out.push_str(rest);
+}
+
+/// `text` 中与之前的 `{` 匹配的 `}` 的位置。
+fn matching_brace(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(index),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// 格式化一个占位符，`inner` 是花括号中的内容。
+fn format_placeholder(
+    out: &mut String,
+    inner: &str,
+    language: &LanguageId,
+    args: &[(&str, TextArg)],
+) {
+    let mut parts = inner.splitn(3, ',').map(str::trim);
+    let name = parts.next().unwrap_or_default();
+    let Some(arg) = args
+        .iter()
+        .find(|(arg, _)| *arg == name)
+        .map(|(_, arg)| arg)
+    else {
+        let _ = write!(out, "{{{inner}}}");
+        return;
+    };
+    match (parts.next(), parts.next()) {
+        (None, _) => out.push_str(&format_number(language, arg)),
+        (Some("plural"), Some(branches)) => {
+            let number = format_number(language, arg);
+            let branch = select_plural(branches, language, arg);
+            format_into(
+                out,
+                branch.unwrap_or(&number),
+                language,
+                args,
+                Some(&number),
+            );
+        }
+        _ => {
+            let _ = write!(out, "{{{inner}}}");
+        }
+    }
+}
+
+/// 从 `one {…} other {…}` 这样的分支中选择与参数匹配的一个。
+fn select_plural<'a>(branches: &'a str, language: &LanguageId, arg: &TextArg) -> Option<&'a str> {
+    let exact = match *arg {
+        TextArg::Int(value) => Some(value),
+        TextArg::Float(value, 0) => Some(value.round() as i64),
+        TextArg::Float(..) | TextArg::Text(_) | TextArg::Key(_) => None,
+    };
+    // 带小数的数字属于 `other`。
+    let category = exact.map_or(PluralCategory::Other, |value| {
+        PluralCategory::of(language, value.unsigned_abs())
+    });
+
+    let mut other = None;
+    let mut matched = None;
+    let mut rest = branches;
+    while let Some(open) = rest.find('{') {
+        let selector = rest[..open].trim();
+        let end = matching_brace(&rest[open + 1..])?;
+        let text = &rest[open + 1..open + 1 + end];
+        rest = &rest[open + end + 2..];
+
+        if let Some(value) = selector.strip_prefix('=') {
+            if exact.is_some() && value.parse().ok() == exact {
+                return Some(text);
+            }
+        } else if selector == category.name() {
+            matched.get_or_insert(text);
+        } else if selector == PluralCategory::Other.name() {
+            other = Some(text);
+        }
+    }
+    matched.or(other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn language(tag: &str) -> LanguageId {
+        LanguageId::parse(tag).unwrap()
+    }
+
+    #[test]
+    fn test_format() {
+        let en = language("en-US");
+        let seeds = "{count, plural, =0 {no seeds} one {# seed} other {# seeds}}";
+        assert_eq!(format(seeds, &en, &[("count", 0.into())]), "no seeds");
+        assert_eq!(format(seeds, &en, &[("count", 1.into())]), "1 seed");
+        assert_eq!(format(seeds, &en, &[("count", 1200.into())]), "1,200 seeds");
+        assert_eq!(
+            format(
+                "Wave {n} of {total}",
+                &en,
+                &[("n", 3.into()), ("total", 10.into())]
+            ),
+            "Wave 3 of 10"
+        );
+        // 缺少的参数和字面的花括号。
+        assert_eq!(format("{{x}} {missing}", &en, &[]), "{x} {missing}");
+
+        let ru = language("ru");
+        let files = "{n, plural, one {# файл} few {# файла} many {# файлов} other {# файла}}";
+        assert_eq!(format(files, &ru, &[("n", 21.into())]), "21 файл");
+        assert_eq!(format(files, &ru, &[("n", 23.into())]), "23 файла");
+        assert_eq!(format(files, &ru, &[("n", 11.into())]), "11 файлов");
+    }
+
+    #[test]
+    fn test_format_number() {
+        let value = TextArg::Float(-1234567.891, 2);
+        assert_eq!(format_number(&language("en-US"), &value), "-1,234,567.89");
+        assert_eq!(format_number(&language("de-DE"), &value), "-1.234.567,89");
+        assert_eq!(format_number(&language("fr"), &value), "-1 234 567,89");
+        assert_eq!(format_number(&language("zh-CN"), &TextArg::Int(999)), "999");
+    }
+}
@@ -0,0 +1,199 @@
+//! 从 `assets/i18n` 加载各语言的文本。
+//!
+//! `assets/i18n/languages.ron` 列出所有可用语言的 BCP-47 标签，每种语言的文本在同一目录下的
+//! `<标签>.locale.ron` 中，包含语言用自身书写的名称和文本 Key 到文本的映射。
+//! 添加翻译只需要添加文本文件并在语言清单中加上它的标签，不需要修改代码。
+//! 语言不通过扫描目录发现：Web 平台上的资源通过 HTTP 加载，无法列出目录中的文件，
+//! 而且清单还决定了语言在选择菜单中的顺序。测试会检查目录中的每个文本文件都在清单中。
+//! 清单中还可以为语言指定后备语言，缺少的文本会依次从后备语言和默认语言中查找。
+//!
+//! 清单或任何文本文件被修改（例如 `dev_native` 下的热重载）后，所有文本会重新加载到 [`LanguageRes`]。
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    platform::collections::HashMap,
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{
+    asset_tracking::{LoadResource, RonAssetLoaderError},
+    i18n::{LanguageId, LanguageRes},
+};
+
+/// 语言清单的路径。
+const LANGUAGES_PATH: &str = "i18n/languages.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Locales>();
+    app.register_asset_loader(LocalesLoader);
+    app.load_resource_from_path::<Locales>(LANGUAGES_PATH);
+
+    app.add_systems(
+        PreUpdate,
+        apply_locales.run_if(resource_exists_and_changed::<Locales>),
+    );
+}
+
+/// 语言清单的内容。
+#[derive(Deserialize)]
+struct LanguageManifest {
+    /// 可用语言的 BCP-47 标签，按语言选择菜单中的顺序排列。
+    languages: Vec<String>,
+    /// 语言标签到它的后备语言标签，例如 `"zh-TW": ["zh-CN"]`。
+    #[serde(default)]
+    fallbacks: HashMap<String, Vec<String>>,
+}
+
+/// 一种语言的文本文件。
+#[derive(Clone, Reflect, Deserialize)]
+pub struct LocaleFile {
+    /// 语言用自身书写的名称，显示在语言选择菜单中。
+    pub name: String,
+    /// 文本 Key（见 [`config`](super::config)）到文本的映射。
+    pub strings: HashMap<String, String>,
+}
+
+impl LocaleFile {
+    fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("`name` is empty".to_string());
+        }
+        match self.strings.iter().find(|(_, value)| value.is_empty()) {
+            Some((key, _)) => Err(format!("`{key}` has an empty text")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// 所有可用语言的文本，按语言清单中的顺序排列。
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct Locales {
+    pub locales: Vec<(LanguageId, LocaleFile)>,
+    pub fallbacks: HashMap<LanguageId, Vec<LanguageId>>,
+}
+
+/// 读取语言清单和其中列出的所有文本文件。无法读取的文本文件会被跳过，不影响其他语言。
+struct LocalesLoader;
+
+impl AssetLoader for LocalesLoader {
+    type Asset = Locales;
+    type Settings = ();
+    type Error = RonAssetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let manifest: LanguageManifest = ron::de::from_bytes(&bytes)?;
+
+        let parse = |tag: &str| {
+            LanguageId::parse(tag).ok_or_else(|| {
+                RonAssetLoaderError::Invalid(format!("`{tag}` is not a valid language tag"))
+            })
+        };
+        let mut fallbacks = HashMap::new();
+        for (tag, tags) in &manifest.fallbacks {
+            let chain = tags
+                .iter()
+                .map(|tag| parse(tag))
+                .collect::<Result<_, _>>()?;
+            fallbacks.insert(parse(tag)?, chain);
+        }
+
+        let mut locales = Vec::new();
+        for tag in manifest.languages {
+            let language = parse(&tag)?;
+            let path = load_context
+                .path()
+                .with_file_name(format!("{tag}.locale.ron"));
+            let file = load_context
+                .read_asset_bytes(path.clone())
+                .await
+                .map_err(|error| error.to_string())
+                .and_then(|bytes| {
+                    ron::de::from_bytes::<LocaleFile>(&bytes).map_err(|error| error.to_string())
+                })
+                .and_then(|file| file.validate().map(|()| file));
+            match file {
+                Ok(file) => locales.push((language, file)),
+                Err(error) => warn!("Skipping locale {}: {error}", path.display()),
+            }
+        }
+        Ok(Locales { locales, fallbacks })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["languages.ron"]
+    }
+}
+
+/// 用加载的文本替换 [`LanguageRes`] 中的所有语言。
+fn apply_locales(locales: Res<Locales>, mut lang_res: ResMut<LanguageRes>) {
+    lang_res.clear_locales();
+    lang_res.set_fallbacks(locales.fallbacks.clone());
+    for (language, file) in &locales.locales {
+        lang_res.set_locale(language.clone(), file.name.clone(), file.strings.clone());
+    }
+    info!("Loaded languages: {:?}", lang_res.language_list);
+
+    // 玩家选择的语言已经不存在时，改用默认语言或第一种可用的语言。
+    if !lang_res.language_list.contains(lang_res.language())
+        && let Some(fallback) = lang_res
+            .language_list
+            .iter()
+            .find(|language| **language == LanguageId::default())
+            .or(lang_res.language_list.first())
+            .cloned()
+    {
+        warn!(
+            "Language {} is not available, using {fallback}",
+            lang_res.language()
+        );
+        lang_res.set_language(fallback);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 清单中的所有文本文件都应该有效，并包含相同的 Key；目录中的文本文件都应该在清单中。
+    #[test]
+    fn test_locale_files() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/i18n");
+        let read = |name: String| std::fs::read_to_string(format!("{dir}/{name}")).unwrap();
+        let manifest: LanguageManifest = ron::from_str(&read("languages.ron".into())).unwrap();
+        let files = manifest
+            .languages
+            .iter()
+            .map(|tag| {
+                assert!(LanguageId::parse(tag).is_some());
+                let file: LocaleFile = ron::from_str(&read(format!("{tag}.locale.ron"))).unwrap();
+                assert!(file.validate().is_ok(), "{tag}");
+                (tag, file)
+            })
+            .collect::<Vec<_>>();
+        let (_, first) = &files[0];
+        for (tag, file) in &files[1..] {
+            for key in first.strings.keys() {
+                assert!(file.strings.contains_key(key), "{tag} lacks `{key}`");
+            }
+            assert_eq!(file.strings.len(), first.strings.len(), "{tag}");
+        }
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let name = entry.unwrap().file_name().into_string().unwrap();
+            if let Some(tag) = name.strip_suffix(".locale.ron") {
+                assert!(
+                    manifest.languages.iter().any(|listed| listed == tag),
+                    "`{name}` is not listed in languages.ron"
+                );
+            }
+        }
+    }
+}
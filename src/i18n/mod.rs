@@ -1,88 +1,268 @@
 //! 多语言支持插件
+//!
+//! 界面通过 [`config`] 中的 Key 从 [`LanguageRes`] 获取当前语言的文本，文本从 [`locale`] 文件加载。
+//! 当前语言缺少某个文本时，依次使用它的后备语言和默认语言中的文本；开发构建中会在第一次遇到时记录警告。
+//! 界面中的固定文本使用 [`LocalizedText`]，切换语言时会自动更新，不需要重新生成界面。
 
 use core::fmt;
+use std::sync::Mutex;
 
-use bevy::{platform::collections::HashMap, prelude::*};
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+pub use self::{format::TextArg, text::LocalizedText};
 
 pub mod config;
+pub mod format;
+pub mod locale;
+pub mod text;
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<LanguageRes>();
-    app.insert_resource::<LanguageRes>(config::init_language_res());
+    app.init_resource::<LanguageRes>();
+    app.add_plugins((locale::plugin, text::plugin));
+}
+
+/// 默认语言，也是找不到玩家选择的语言时使用的语言。
+const DEFAULT_LANGUAGE: &str = "en-US";
+
+/// 多语言Id，即语言的 BCP-47 标签，例如 `en-US`、`zh-CN`。
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Reflect, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LanguageId(String);
+
+impl LanguageId {
+    /// 解析一个 BCP-47 标签。这里只检查基本格式：由 `-` 分隔的若干段，每段是 1 到 8 个字母或数字，
+    /// 第一段（语言）是 2 到 8 个字母。
+    pub fn parse(tag: &str) -> Option<Self> {
+        let mut subtags = tag.split('-');
+        let language = subtags.next()?;
+        let valid = (2..=8).contains(&language.len())
+            && language.chars().all(|c| c.is_ascii_alphabetic())
+            && subtags.all(|subtag| {
+                (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+            });
+        valid.then(|| Self(tag.to_string()))
+    }
 }
 
-/// 多语言Id
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, Reflect)]
-pub enum LanguageId {
-    ZhCn, // 简体中文
-    #[default]
-    EnUs, // 英语
+impl Default for LanguageId {
+    fn default() -> Self {
+        Self(DEFAULT_LANGUAGE.to_string())
+    }
 }
 
-/// 实现 Display trait 以便于打印 和显示对应的语言名称
+/// 实现 Display trait 以便于打印
 impl fmt::Display for LanguageId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            LanguageId::ZhCn => write!(f, "简体中文"),
-            LanguageId::EnUs => write!(f, "English"),
-        }
+        f.write_str(&self.0)
     }
 }
 
 /// 多语言配置
-#[derive(Resource, Asset, Clone, Reflect)]
+#[derive(Resource, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct LanguageRes {
-    pub curr_language: LanguageId,
+    /// 当前语言，通过 [`set_language`](Self::set_language) 切换。
+    curr_language: LanguageId,
+    /// 所有可用的语言，按语言清单中的顺序排列。
     pub language_list: Vec<LanguageId>,
+    /// 各语言用自身书写的名称，例如 `简体中文`。
+    pub language_names: HashMap<LanguageId, String>,
     pub language_hash: HashMap<LanguageId, HashMap<String, String>>,
+    /// 各语言缺少文本时依次尝试的语言，例如 `zh-TW` 使用 `zh-CN`。最后总是尝试默认语言。
+    fallbacks: HashMap<LanguageId, Vec<LanguageId>>,
+    /// 当前语言的后备链，切换语言或修改后备语言时重新计算。
+    chain: Vec<LanguageId>,
+    /// 开发构建中已经报告过的缺少的文本。
+    #[reflect(ignore)]
+    reported_missing: ReportedTexts,
 }
 
 impl Default for LanguageRes {
     fn default() -> Self {
-        Self {
-            curr_language: Default::default(),
-            language_list: vec![LanguageId::ZhCn, LanguageId::EnUs],
+        let mut lang_res = Self {
+            curr_language: LanguageId::default(),
+            language_list: Vec::new(),
+            language_names: HashMap::new(),
             language_hash: HashMap::new(),
-        }
+            fallbacks: HashMap::new(),
+            chain: Vec::new(),
+            reported_missing: ReportedTexts::default(),
+        };
+        lang_res.update_chain();
+        lang_res
     }
 }
 
 impl LanguageRes {
-    #[allow(dead_code)]
+    /// 当前语言。
+    pub fn language(&self) -> &LanguageId {
+        &self.curr_language
+    }
+
+    /// 切换当前语言。语言文件还没有加载时接受任何语言，加载后会检查它是否可用。
     pub fn set_language(&mut self, language: LanguageId) {
-        if self.language_list.contains(&language) {
+        if self.language_list.is_empty() || self.language_list.contains(&language) {
             self.curr_language = language;
+            self.update_chain();
         } else {
             warn!("Unsupported language: {}", language);
         }
     }
 
-    pub fn zh_cn(&mut self, key: &str, value: &str) {
-        let language = LanguageId::ZhCn;
-        self.add(language, key, value);
+    /// 设置各语言的后备语言。
+    pub fn set_fallbacks(&mut self, fallbacks: HashMap<LanguageId, Vec<LanguageId>>) {
+        self.fallbacks = fallbacks;
+        self.update_chain();
     }
 
-    pub fn en_us(&mut self, key: &str, value: &str) {
-        let language = LanguageId::EnUs;
-        self.add(language, key, value);
+    /// 删除所有语言的名称和文本，准备重新加载语言文件。当前语言保持不变。
+    pub fn clear_locales(&mut self) {
+        self.language_list.clear();
+        self.language_names.clear();
+        self.language_hash.clear();
+        self.set_fallbacks(HashMap::new());
+        self.reported_missing.clear();
     }
 
-    pub fn add(&mut self, language: LanguageId, key: &str, value: &str) {
+    fn update_chain(&mut self) {
+        self.chain = self.fallback_chain(&self.curr_language);
+    }
+
+    /// 添加一种语言，或者替换这种语言的名称和所有文本。
+    pub fn set_locale(
+        &mut self,
+        language: LanguageId,
+        name: String,
+        texts: HashMap<String, String>,
+    ) {
         if !self.language_list.contains(&language) {
-            self.language_list.push(language);
+            self.language_list.push(language.clone());
         }
-        let map = self.language_hash.entry(language).or_default();
-        map.insert(key.into(), value.into());
+        self.language_names.insert(language.clone(), name);
+        self.language_hash.insert(language, texts);
     }
 
-    /// 设置当前语言对应的文本
-    pub fn get(&self, key: &str) -> String {
-        self.language_hash
-            .get(&self.curr_language)
-            .and_then(|map| map.get(key))
+    /// 语言用自身书写的名称，未知的语言使用它的标签。
+    pub fn language_name(&self, language: &LanguageId) -> String {
+        self.language_names
+            .get(language)
             .cloned()
-            .unwrap_or_else(|| key.to_string())
+            .unwrap_or_else(|| language.to_string())
+    }
+
+    /// 查找 `language` 的文本时依次尝试的语言：它自己、它的后备语言，最后是默认语言。
+    fn fallback_chain(&self, language: &LanguageId) -> Vec<LanguageId> {
+        let mut chain = vec![language.clone()];
+        let fallbacks = self.fallbacks.get(language).into_iter().flatten().cloned();
+        for language in fallbacks.chain([LanguageId::default()]) {
+            if !chain.contains(&language) {
+                chain.push(language);
+            }
+        }
+        chain
+    }
+
+    /// 在当前语言的后备链中查找 `key` 对应的文本。
+    fn lookup(&self, key: &str) -> Option<&String> {
+        self.chain
+            .iter()
+            .find_map(|language| self.language_hash.get(language)?.get(key))
+    }
+
+    /// 当前语言或其后备语言是否包含 `key` 对应的文本
+    pub fn contains(&self, key: &str) -> bool {
+        self.lookup(key).is_some()
+    }
+
+    /// 获取当前语言对应的文本，缺少时使用后备语言的文本，再使用 [`config::BUILTIN_TEXTS`]，
+    /// 都没有时返回 `key`。
+    pub fn get(&self, key: &str) -> String {
+        #[cfg(feature = "dev")]
+        if !self.language_hash.is_empty()
+            && !self
+                .language_hash
+                .get(&self.curr_language)
+                .is_some_and(|map| map.contains_key(key))
+        {
+            self.reported_missing.report(&self.curr_language, key);
+        }
+        self.lookup(key)
+            .map(String::as_str)
+            .or_else(|| {
+                config::BUILTIN_TEXTS
+                    .iter()
+                    .find_map(|&(builtin, text)| (builtin == key).then_some(text))
+            })
+            .unwrap_or(key)
+            .to_string()
+    }
+
+    /// 与默认语言相比，`language` 缺少的文本 Key，按字母顺序排列。
+    #[cfg(any(feature = "dev", test))]
+    pub fn missing_keys(&self, language: &LanguageId) -> Vec<&str> {
+        let Some(reference) = self.language_hash.get(&LanguageId::default()) else {
+            return Vec::new();
+        };
+        let texts = self.language_hash.get(language);
+        let mut missing = reference
+            .keys()
+            .filter(|key| !texts.is_some_and(|texts| texts.contains_key(*key)))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        missing.sort_unstable();
+        missing
+    }
+
+    /// 获取当前语言对应的文本，并用 `args` 替换其中的占位符，语法见 [`format`](mod@format)。
+    /// [`TextArg::Key`] 参数先替换为当前语言中的文本。
+    pub fn get_args(&self, key: &str, args: &[(&str, TextArg)]) -> String {
+        let pattern = self.get(key);
+        if !args.iter().any(|(_, arg)| matches!(arg, TextArg::Key(_))) {
+            return format::format(&pattern, &self.curr_language, args);
+        }
+        let args = args
+            .iter()
+            .map(|(name, arg)| match arg {
+                TextArg::Key(key) => (*name, TextArg::Text(self.get(key))),
+                arg => (*name, arg.clone()),
+            })
+            .collect::<Vec<_>>();
+        format::format(&pattern, &self.curr_language, &args)
+    }
+}
+
+/// 已经报告过的缺少的文本。[`LanguageRes::get`] 只有共享引用，所以使用互斥锁。
+#[derive(Default)]
+struct ReportedTexts(Mutex<HashSet<(LanguageId, String)>>);
+
+impl ReportedTexts {
+    /// 记录缺少的文本，每种语言的每个 Key 只记录一次。
+    #[cfg(feature = "dev")]
+    fn report(&self, language: &LanguageId, key: &str) {
+        let Ok(mut reported) = self.0.lock() else {
+            return;
+        };
+        if reported.insert((language.clone(), key.to_string())) {
+            warn!("Missing text `{key}` for {language}");
+        }
+    }
+
+    fn clear(&self) {
+        if let Ok(mut reported) = self.0.lock() {
+            reported.clear();
+        }
+    }
+}
+
+impl Clone for ReportedTexts {
+    fn clone(&self) -> Self {
+        let reported = self.0.lock().map(|reported| reported.clone());
+        Self(Mutex::new(reported.unwrap_or_default()))
     }
 }
 
@@ -90,25 +270,114 @@ impl LanguageRes {
 mod tests {
     use super::*; // 引入当前模块的所有内容
 
+    fn language(tag: &str) -> LanguageId {
+        LanguageId::parse(tag).unwrap()
+    }
+
+    fn test_res() -> LanguageRes {
+        let mut lang_res = LanguageRes::default();
+        lang_res.set_locale(
+            language("zh-CN"),
+            "简体中文".to_string(),
+            HashMap::from_iter([("HELLO".to_string(), "你好".to_string())]),
+        );
+        lang_res.set_locale(
+            language("en-US"),
+            "English".to_string(),
+            HashMap::from_iter([("HELLO".to_string(), "Hello".to_string())]),
+        );
+        lang_res
+    }
+
     #[test]
     fn test_language_res_get() {
-        let mut lang_res = LanguageRes::default();
-        lang_res.zh_cn("HELLO", "你好");
-        lang_res.en_us("HELLO", "Hello");
+        let mut lang_res = test_res();
 
         assert_eq!(lang_res.get("HELLO"), "Hello"); // 默认语言为英文
-        lang_res.set_language(LanguageId::ZhCn);
+        lang_res.set_language(language("zh-CN"));
         assert_eq!(lang_res.get("HELLO"), "你好"); // 切换到中文
         assert_eq!(lang_res.get("UNKNOWN_KEY"), "UNKNOWN_KEY"); // 未知 Key 返回原始值
+        assert_eq!(lang_res.language_name(&language("zh-CN")), "简体中文");
+    }
+
+    #[test]
+    fn test_builtin_texts() {
+        // 语言文件加载之前使用内置的文本。
+        assert_eq!(LanguageRes::default().get(config::LOADING), "Loading...");
+        let mut lang_res = test_res();
+        lang_res.set_locale(
+            language("en-US"),
+            "English".to_string(),
+            HashMap::from_iter([(config::LOADING.to_string(), "Please wait".to_string())]),
+        );
+        assert_eq!(lang_res.get(config::LOADING), "Please wait");
     }
 
     #[test]
     fn test_set_language() {
-        let mut lang_res = LanguageRes::default();
-        lang_res.set_language(LanguageId::EnUs);
-        assert_eq!(lang_res.curr_language, LanguageId::EnUs);
+        let mut lang_res = test_res();
+        lang_res.set_language(language("zh-CN"));
+        assert_eq!(lang_res.curr_language, language("zh-CN"));
+
+        // 不可用的语言被忽略。
+        lang_res.set_language(language("ja-JP"));
+        assert_eq!(lang_res.curr_language, language("zh-CN"));
+    }
+
+    #[test]
+    fn test_fallback_chain() {
+        let mut lang_res = test_res();
+        let mut texts = HashMap::new();
+        texts.insert("HELLO".to_string(), "Hello".to_string());
+        texts.insert("BYE".to_string(), "Bye".to_string());
+        lang_res.set_locale(language("en-US"), "English".to_string(), texts);
+        lang_res.set_locale(language("zh-TW"), "繁體中文".to_string(), HashMap::new());
+        lang_res.set_fallbacks(HashMap::from_iter([(
+            language("zh-TW"),
+            vec![language("zh-CN")],
+        )]));
 
-        lang_res.set_language(LanguageId::ZhCn);
-        assert_eq!(lang_res.curr_language, LanguageId::ZhCn);
+        assert_eq!(
+            lang_res.fallback_chain(&language("zh-TW")),
+            [language("zh-TW"), language("zh-CN"), language("en-US")]
+        );
+        lang_res.set_language(language("zh-TW"));
+        assert_eq!(lang_res.get("HELLO"), "你好");
+        assert_eq!(lang_res.get("BYE"), "Bye");
+        assert!(lang_res.contains("BYE"));
+        // 修改后备语言后重新计算当前语言的后备链。
+        lang_res.set_fallbacks(HashMap::new());
+        assert_eq!(lang_res.get("HELLO"), "Hello");
+
+        assert_eq!(lang_res.missing_keys(&language("zh-TW")), ["BYE", "HELLO"]);
+        assert_eq!(lang_res.missing_keys(&language("zh-CN")), ["BYE"]);
+        assert!(lang_res.missing_keys(&language("en-US")).is_empty());
+    }
+
+    #[cfg(feature = "dev")]
+    #[test]
+    fn test_report_missing_text() {
+        let mut lang_res = test_res();
+        let reported = |lang_res: &LanguageRes| lang_res.reported_missing.0.lock().unwrap().len();
+        lang_res.get("UNKNOWN_KEY");
+        lang_res.get("UNKNOWN_KEY");
+        assert_eq!(reported(&lang_res), 1);
+        lang_res.set_language(language("zh-CN"));
+        lang_res.get("UNKNOWN_KEY");
+        assert_eq!(reported(&lang_res), 2);
+        // 重新加载语言文件后会再次报告。
+        lang_res.clear_locales();
+        assert_eq!(reported(&lang_res), 0);
+    }
+
+    #[test]
+    fn test_parse_language_id() {
+        assert!(LanguageId::parse("en").is_some());
+        assert!(LanguageId::parse("zh-Hant-TW").is_some());
+        assert!(LanguageId::parse("es-419").is_some());
+        assert!(LanguageId::parse("").is_none());
+        assert!(LanguageId::parse("e").is_none());
+        assert!(LanguageId::parse("en-").is_none());
+        assert!(LanguageId::parse("../en").is_none());
     }
 }
@@ -0,0 +1,128 @@
+//! 随当前语言自动更新的界面文本。
+//!
+//! 给文本实体添加 [`LocalizedText`] 后，它的 [`Text`] 由文本 Key 和参数生成，
+//! 并在 [`LanguageRes`] 或 [`LocalizedText`] 改变时重新生成。
+//! 因此切换语言后，已经打开的菜单和 HUD 会直接显示新语言的文本。
+
+use std::borrow::Cow;
+
+use bevy::prelude::*;
+
+use crate::i18n::{LanguageRes, TextArg};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<LocalizedText>();
+
+    // 在 UI 布局计算文本大小之前更新，这样新生成的文本在第一帧就是正确的。
+    app.add_systems(
+        PostUpdate,
+        update_localized_texts.before(bevy::ui::UiSystems::Prepare),
+    );
+}
+
+/// 由文本 Key 和参数生成的 [`Text`]，语法见 [`format`](mod@super::format)。
+///
+/// 要更新参数，用 [`DetectChangesMut::set_if_neq`] 替换整个组件，这样参数不变时不会重新生成文本。
+#[derive(Component, Reflect, Clone, Debug, PartialEq)]
+#[reflect(Component)]
+#[require(Text)]
+pub struct LocalizedText {
+    /// 文本 Key（见 [`config`](super::config)）。
+    pub key: Cow<'static, str>,
+    pub args: Vec<(&'static str, TextArg)>,
+}
+
+impl LocalizedText {
+    pub fn new(key: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            key: key.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// 添加一个参数。
+    pub fn with_arg(mut self, name: &'static str, value: impl Into<TextArg>) -> Self {
+        self.args.push((name, value.into()));
+        self
+    }
+
+    /// 当前语言中的文本。
+    pub fn text(&self, lang_res: &LanguageRes) -> String {
+        if self.args.is_empty() {
+            lang_res.get(&self.key)
+        } else {
+            lang_res.get_args(&self.key, &self.args)
+        }
+    }
+}
+
+fn update_localized_texts(
+    lang_res: Res<LanguageRes>,
+    mut text_query: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+    let language_changed = lang_res.is_changed();
+    for (localized, mut text) in &mut text_query {
+        if language_changed || localized.is_changed() {
+            let value = localized.text(&lang_res);
+            if text.0 != value {
+                text.0 = value;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::platform::collections::HashMap;
+
+    use super::*;
+    use crate::i18n::LanguageId;
+
+    #[test]
+    fn test_update_localized_texts() {
+        let mut lang_res = LanguageRes::default();
+        for (tag, text, tool) in [
+            ("en-US", "{n} seeds", "Hoe"),
+            ("zh-CN", "{n} 颗种子", "锄头"),
+        ] {
+            let language = LanguageId::parse(tag).unwrap();
+            let texts = HashMap::from_iter([
+                ("SEEDS".to_string(), text.to_string()),
+                ("TOOL".to_string(), "{tool}".to_string()),
+                ("HOE".to_string(), tool.to_string()),
+            ]);
+            lang_res.set_locale(language, tag.to_string(), texts);
+        }
+        let mut app = App::new();
+        app.insert_resource(lang_res);
+        app.add_systems(Update, update_localized_texts);
+        let entity = app
+            .world_mut()
+            .spawn(LocalizedText::new("SEEDS").with_arg("n", 3))
+            .id();
+        // 参数本身也是文本 Key 时，同样随语言更新。
+        let tool = app
+            .world_mut()
+            .spawn(LocalizedText::new("TOOL").with_arg("tool", TextArg::Key("HOE".into())))
+            .id();
+        let text = |app: &App| app.world().get::<Text>(entity).unwrap().0.clone();
+
+        app.update();
+        assert_eq!(text(&app), "3 seeds");
+        assert_eq!(app.world().get::<Text>(tool).unwrap().0, "Hoe");
+
+        app.world_mut()
+            .resource_mut::<LanguageRes>()
+            .set_language(LanguageId::parse("zh-CN").unwrap());
+        app.update();
+        assert_eq!(text(&app), "3 颗种子");
+        assert_eq!(app.world().get::<Text>(tool).unwrap().0, "锄头");
+
+        app.world_mut()
+            .get_mut::<LocalizedText>(entity)
+            .unwrap()
+            .set_if_neq(LocalizedText::new("SEEDS").with_arg("n", 4));
+        app.update();
+        assert_eq!(text(&app), "4 颗种子");
+    }
+}
@@ -10,7 +10,10 @@ mod dev_tools;
 mod games;
 mod i18n;
 mod menus;
+mod save;
 mod screens;
+mod settings;
+mod storage;
 mod theme;
 
 use bevy::{asset::AssetMetaCheck, prelude::*};
@@ -48,14 +51,16 @@ impl Plugin for AppPlugin {
 
         // 添加其他插件。
         app.add_plugins((
-            i18n::plugin,
             asset_tracking::plugin,
+            i18n::plugin,
             audio::plugin,
+            save::plugin,
             games::plugin,
             #[cfg(feature = "dev")]
             dev_tools::plugin,
             menus::plugin,
             screens::plugin,
+            settings::plugin,
             theme::plugin,
         ));
 
@@ -6,9 +6,8 @@ use bevy::{
 
 use crate::{
     FntAssets,
-    asset_tracking::LoadResource,
-    audio::music,
-    i18n::{LanguageRes, config::BACK},
+    audio::AddStateMusic,
+    i18n::{LocalizedText, config::BACK},
     menus::Menu,
     theme::prelude::*,
 };
@@ -19,17 +18,10 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         go_back.run_if(in_state(Menu::Credits).and(input_just_pressed(KeyCode::Escape))),
     );
-
-    app.register_type::<CreditsAssets>();
-    app.load_resource::<CreditsAssets>();
-    app.add_systems(OnEnter(Menu::Credits), start_credits_music);
+    app.add_state_music(Menu::Credits, &["audio/music/Monkeys Spinning Monkeys.ogg"]);
 }
 
-fn spawn_credits_menu(
-    mut commands: Commands,
-    font_res: Res<FntAssets>,
-    lang_res: Res<LanguageRes>,
-) {
+fn spawn_credits_menu(mut commands: Commands, font_res: Res<FntAssets>) {
     commands.spawn((
         widget::ui_root("Credits Menu"),
         GlobalZIndex(2),
@@ -39,7 +31,7 @@ fn spawn_credits_menu(
             created_by(font_res.pixel()),
             widget::header("Assets", font_res.pixel()),
             assets(font_res.pixel()),
-            widget::button(lang_res.get(BACK), font_res.pixel(), go_back_on_click),
+            widget::button(LocalizedText::new(BACK), font_res.pixel(), go_back_on_click),
         ],
     ));
 }
@@ -104,27 +96,3 @@ fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>
 fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Main);
 }
-
-#[derive(Resource, Asset, Clone, Reflect)]
-#[reflect(Resource)]
-struct CreditsAssets {
-    #[dependency]
-    music: Handle<AudioSource>,
-}
-
-impl FromWorld for CreditsAssets {
-    fn from_world(world: &mut World) -> Self {
-        let assets = world.resource::<AssetServer>();
-        Self {
-            music: assets.load("audio/music/Monkeys Spinning Monkeys.ogg"),
-        }
-    }
-}
-
-fn start_credits_music(mut commands: Commands, credits_music: Res<CreditsAssets>) {
-    commands.spawn((
-        Name::new("Credits Music"),
-        DespawnOnExit(Menu::Credits),
-        music(credits_music.music.clone()),
-    ));
-}
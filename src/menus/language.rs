@@ -0,0 +1,82 @@
+//! 语言选择菜单，列出所有可用的语言（见 [`locale`](crate::i18n::locale)）。
+//!
+//! 选择的语言立即生效，界面中的文本会原地更新（见 [`LocalizedText`]）。
+
+use bevy::{
+    ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{
+    FntAssets,
+    i18n::{
+        LanguageRes, LocalizedText,
+        config::{BACK, LANGUAGE_TITLE},
+    },
+    menus::Menu,
+    settings::Settings,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Language), spawn_language_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Language).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_language_menu(
+    mut commands: Commands,
+    font_res: Res<FntAssets>,
+    lang_res: Res<LanguageRes>,
+) {
+    let font = font_res.pixel();
+    let languages = lang_res
+        .language_list
+        .iter()
+        .map(|language| (language.clone(), lang_res.language_name(language)))
+        .collect::<Vec<_>>();
+    let list_font = font.clone();
+
+    commands.spawn((
+        widget::ui_root("Language Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Language),
+        children![
+            widget::header(LocalizedText::new(LANGUAGE_TITLE), font.clone()),
+            (
+                Name::new("Language List"),
+                Node {
+                    display: Display::Grid,
+                    row_gap: Px(10.0),
+                    column_gap: Px(30.0),
+                    grid_template_columns: RepeatedGridTrack::px(2, 300.0),
+                    ..default()
+                },
+                Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+                    for (language, name) in languages {
+                        parent.spawn(widget::button(
+                            name,
+                            list_font.clone(),
+                            move |_: On<Pointer<Click>>,
+                                  mut settings: ResMut<Settings>,
+                                  mut next_menu: ResMut<NextState<Menu>>| {
+                                settings.language = language.clone();
+                                next_menu.set(Menu::Main);
+                            },
+                        ));
+                    }
+                })),
+            ),
+            widget::button(LocalizedText::new(BACK), font, go_back_on_click),
+        ],
+    ));
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
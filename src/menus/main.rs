@@ -4,9 +4,8 @@ use bevy::prelude::*;
 
 use crate::{
     FntAssets,
-    i18n::{LanguageId, LanguageRes, config::*},
+    i18n::{LanguageRes, LocalizedText, config::*},
     menus::Menu,
-    screens::Screen,
     theme::widget,
 };
 
@@ -69,36 +68,46 @@ fn spawn_main_menu(
                         #[cfg(not(target_family = "wasm"))]
                         children![
                             widget::button(
-                                lang_res.get(MAIN_PLAY),
+                                LocalizedText::new(MAIN_PLAY),
                                 font.clone(),
                                 enter_loading_or_gameplay_screen,
                             ),
                             widget::button(
-                                lang_res.get(MAIN_SETTINGS),
+                                LocalizedText::new(LOAD_TITLE),
+                                font.clone(),
+                                open_load_menu,
+                            ),
+                            widget::button(
+                                LocalizedText::new(MAIN_SETTINGS),
                                 font.clone(),
                                 open_settings_menu,
                             ),
                             widget::button(
-                                lang_res.get(MAIN_CREDITS),
+                                LocalizedText::new(MAIN_CREDITS),
                                 font.clone(),
                                 open_credits_menu
                             ),
-                            widget::button(lang_res.get(MAIN_EXIT), font.clone(), exit_app),
+                            widget::button(LocalizedText::new(MAIN_EXIT), font.clone(), exit_app),
                         ],
                         #[cfg(target_family = "wasm")]
                         children![
                             widget::button(
-                                lang_res.get(MAIN_PLAY),
+                                LocalizedText::new(MAIN_PLAY),
                                 font.clone(),
                                 enter_loading_or_gameplay_screen
                             ),
                             widget::button(
-                                lang_res.get(MAIN_SETTINGS),
+                                LocalizedText::new(LOAD_TITLE),
+                                font.clone(),
+                                open_load_menu
+                            ),
+                            widget::button(
+                                LocalizedText::new(MAIN_SETTINGS),
                                 font.clone(),
                                 open_settings_menu
                             ),
                             widget::button(
-                                lang_res.get(MAIN_CREDITS),
+                                LocalizedText::new(MAIN_CREDITS),
                                 font.clone(),
                                 open_credits_menu
                             ),
@@ -114,10 +123,10 @@ fn spawn_main_menu(
                         children![
                             ImageNode::new(asset_server.load("images/icon/language.png")),
                             widget::button_size(
-                                lang_res.curr_language.to_string(),
+                                lang_res.language_name(lang_res.language()),
                                 font.clone(),
                                 Vec2::new(190.0, 40.0),
-                                click_language_button
+                                open_language_menu
                             ),
                         ],
                     )
@@ -134,7 +143,7 @@ fn spawn_main_menu(
                     ..default()
                 },
                 children![widget::label_size_80(
-                    lang_res.get(GAME_TITLE),
+                    LocalizedText::new(GAME_TITLE),
                     font.clone(),
                 ),],
             ),
@@ -157,6 +166,10 @@ fn enter_loading_or_gameplay_screen(
     // }
 }
 
+fn open_load_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Load);
+}
+
 fn open_settings_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
@@ -170,16 +183,6 @@ fn exit_app(_: On<Pointer<Click>>, mut app_exit: MessageWriter<AppExit>) {
     app_exit.write(AppExit::Success);
 }
 
-fn click_language_button(
-    _: On<Pointer<Click>>,
-    mut lang_res: ResMut<LanguageRes>,
-    mut next_screen: ResMut<NextState<Screen>>,
-) {
-    let next_language = if lang_res.curr_language == LanguageId::ZhCn {
-        LanguageId::EnUs
-    } else {
-        LanguageId::ZhCn
-    };
-    lang_res.set_language(next_language);
-    next_screen.set(Screen::RefreshLanguage);
+fn open_language_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Language);
 }
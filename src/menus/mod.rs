@@ -1,10 +1,14 @@
 //! 游戏的菜单及其之间的过渡。
 
 mod credits;
+mod language;
 mod main;
 mod pause;
+mod results;
+mod save;
 mod select_game;
 mod settings;
+mod shop;
 
 use bevy::prelude::*;
 
@@ -13,10 +17,14 @@ pub(super) fn plugin(app: &mut App) {
 
     app.add_plugins((
         credits::plugin,
+        language::plugin,
         main::plugin,
         settings::plugin,
         pause::plugin,
+        results::plugin,
+        save::plugin,
         select_game::plugin,
+        shop::plugin,
     ));
 }
 
@@ -28,6 +36,11 @@ pub enum Menu {
     Main,
     Credits,
     Settings,
+    Language,
     SelectGame,
     Pause,
+    Results,
+    Shop,
+    Save,
+    Load,
 }
@@ -4,9 +4,13 @@ use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
 use crate::{
     FntAssets,
+    games::mini_game::{ActiveGame, MiniGameRegistry},
     i18n::{
-        LanguageRes,
-        config::{MAIN_SETTINGS, PAUSE_CONTINUE, PAUSE_GAME_TITLE, PAUSE_QUIT_TO_TITLE},
+        LocalizedText,
+        config::{
+            LOAD_TITLE, MAIN_SETTINGS, PAUSE_CONTINUE, PAUSE_GAME_TITLE, PAUSE_QUIT_TO_TITLE,
+            SAVE_TITLE,
+        },
     },
     menus::Menu,
     screens::Screen,
@@ -21,28 +25,66 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-fn spawn_pause_menu(mut commands: Commands, font_res: Res<FntAssets>, lang_res: Res<LanguageRes>) {
+fn spawn_pause_menu(
+    mut commands: Commands,
+    font_res: Res<FntAssets>,
+    active_game: Option<Res<State<ActiveGame>>>,
+    registry: Res<MiniGameRegistry>,
+) {
+    // 不能保存的游戏不显示保存按钮，读取存档仍然可用。
+    let saveable = active_game.is_some_and(|active_game| match active_game.get() {
+        ActiveGame::Running(game) => registry.is_saveable(game),
+        ActiveGame::None => false,
+    });
+    let font = font_res.pixel();
     commands.spawn((
         widget::ui_root("Pause Menu"),
         GlobalZIndex(2),
         DespawnOnExit(Menu::Pause),
-        children![
-            widget::header(lang_res.get(PAUSE_GAME_TITLE), font_res.pixel()),
-            widget::button(lang_res.get(PAUSE_CONTINUE), font_res.pixel(), close_menu),
-            widget::button(
-                lang_res.get(MAIN_SETTINGS),
-                font_res.pixel(),
-                open_settings_menu
-            ),
-            widget::button(
-                lang_res.get(PAUSE_QUIT_TO_TITLE),
-                font_res.pixel(),
-                quit_to_title
-            ),
-        ],
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent.spawn(widget::header(
+                LocalizedText::new(PAUSE_GAME_TITLE),
+                font.clone(),
+            ));
+            parent.spawn(widget::button(
+                LocalizedText::new(PAUSE_CONTINUE),
+                font.clone(),
+                close_menu,
+            ));
+            if saveable {
+                parent.spawn(widget::button(
+                    LocalizedText::new(SAVE_TITLE),
+                    font.clone(),
+                    open_save_menu,
+                ));
+            }
+            parent.spawn(widget::button(
+                LocalizedText::new(LOAD_TITLE),
+                font.clone(),
+                open_load_menu,
+            ));
+            parent.spawn(widget::button(
+                LocalizedText::new(MAIN_SETTINGS),
+                font.clone(),
+                open_settings_menu,
+            ));
+            parent.spawn(widget::button(
+                LocalizedText::new(PAUSE_QUIT_TO_TITLE),
+                font,
+                quit_to_title,
+            ));
+        })),
     ));
 }
 
+fn open_save_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Save);
+}
+
+fn open_load_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Load);
+}
+
 fn open_settings_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
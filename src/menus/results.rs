@@ -0,0 +1,101 @@
+//! 一局游戏结束后的结算菜单。
+
+use bevy::{ecs::spawn::SpawnWith, prelude::*, ui::Val::*};
+
+use crate::{
+    FntAssets,
+    games::mini_game::{GameOutcome, RunStats},
+    i18n::{
+        LocalizedText,
+        config::{
+            RESULTS_DEFEAT, RESULTS_RETRY, RESULTS_SELECT_GAME, RESULTS_TIME, RESULTS_VICTORY,
+        },
+    },
+    menus::Menu,
+    screens::{Screen, title::TitleMenu},
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Results), spawn_results_menu);
+}
+
+fn spawn_results_menu(mut commands: Commands, font_res: Res<FntAssets>, stats: Res<RunStats>) {
+    let title = match stats.outcome {
+        Some(GameOutcome::Victory) => RESULTS_VICTORY,
+        Some(GameOutcome::Defeat) | None => RESULTS_DEFEAT,
+    };
+    let seconds = stats.elapsed.as_secs();
+    let rows = stats
+        .counters
+        .iter()
+        .map(|(key, value)| (LocalizedText::new(*key), value.to_string()))
+        .chain([(
+            LocalizedText::new(RESULTS_TIME),
+            format!("{}:{:02}", seconds / 60, seconds % 60),
+        )])
+        .collect();
+
+    commands.spawn((
+        widget::ui_root("Results Menu"),
+        GlobalZIndex(2),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        DespawnOnExit(Menu::Results),
+        children![
+            widget::header(LocalizedText::new(title), font_res.pixel()),
+            summary(rows, font_res.pixel()),
+            widget::button(LocalizedText::new(RESULTS_RETRY), font_res.pixel(), retry),
+            widget::button(
+                LocalizedText::new(RESULTS_SELECT_GAME),
+                font_res.pixel(),
+                open_select_game_menu
+            ),
+        ],
+    ));
+}
+
+/// 统计表：每行左边是统计项的名称，右边是数值。
+fn summary(rows: Vec<(LocalizedText, String)>, font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Summary"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            grid_template_columns: RepeatedGridTrack::px(2, 300.0),
+            ..default()
+        },
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            for (name, value) in rows {
+                parent.spawn((
+                    widget::label(name, font.clone()),
+                    Node {
+                        justify_self: JustifySelf::End,
+                        ..default()
+                    },
+                ));
+                parent.spawn((
+                    widget::label(value, font.clone()),
+                    Node {
+                        justify_self: JustifySelf::Start,
+                        ..default()
+                    },
+                ));
+            }
+        })),
+    )
+}
+
+/// 重新开始同一个游戏。
+fn retry(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Loading);
+}
+
+fn open_select_game_menu(
+    _: On<Pointer<Click>>,
+    mut title_menu: ResMut<TitleMenu>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    title_menu.0 = Menu::SelectGame;
+    next_screen.set(Screen::Title);
+}
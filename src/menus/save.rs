@@ -0,0 +1,207 @@
+//! 存档菜单：选择一个存档位保存或读取游戏。
+//!
+//! 保存菜单从暂停菜单打开，读取菜单可以从主菜单或暂停菜单打开。
+//! 每个存档位显示所属的游戏、保存时间（UTC）和游戏时间。
+
+use bevy::{
+    ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{
+    FntAssets,
+    games::game_list::GameList,
+    i18n::{
+        LocalizedText, TextArg,
+        config::{
+            BACK, LOAD_TITLE, SAVE_EMPTY, SAVE_LOAD, SAVE_SAVE, SAVE_SLOT, SAVE_SLOT_SAVED,
+            SAVE_TITLE,
+        },
+    },
+    menus::Menu,
+    save::{SLOT_COUNT, SaveMeta, SaveSlots, load_game, save_game},
+    screens::Screen,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<SlotLabel>();
+
+    app.add_systems(OnEnter(Menu::Save), spawn_save_menu);
+    app.add_systems(OnEnter(Menu::Load), spawn_load_menu);
+    app.add_systems(
+        Update,
+        (
+            update_slot_labels.run_if(resource_changed::<SaveSlots>),
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+        )
+            .run_if(in_state(Menu::Save).or(in_state(Menu::Load))),
+    );
+}
+
+/// 显示一个存档位元数据的标签。
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+struct SlotLabel(usize);
+
+fn spawn_save_menu(commands: Commands, font_res: Res<FntAssets>, slots: ResMut<SaveSlots>) {
+    spawn_slot_menu(commands, Menu::Save, &font_res, slots);
+}
+
+fn spawn_load_menu(commands: Commands, font_res: Res<FntAssets>, slots: ResMut<SaveSlots>) {
+    spawn_slot_menu(commands, Menu::Load, &font_res, slots);
+}
+
+fn spawn_slot_menu(
+    mut commands: Commands,
+    menu: Menu,
+    font_res: &FntAssets,
+    mut slots: ResMut<SaveSlots>,
+) {
+    slots.refresh();
+    let font = font_res.pixel();
+    let (title, action) = if menu == Menu::Save {
+        (SAVE_TITLE, SAVE_SAVE)
+    } else {
+        (LOAD_TITLE, SAVE_LOAD)
+    };
+    // 读取菜单中的空存档位没有按钮。
+    let available = (0..SLOT_COUNT)
+        .map(|slot| menu == Menu::Save || slots.0.get(slot).is_some_and(Option::is_some))
+        .collect::<Vec<_>>();
+    let grid_font = font.clone();
+
+    commands.spawn((
+        widget::ui_root("Save Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(menu),
+        children![
+            widget::header(LocalizedText::new(title), font.clone()),
+            (
+                Name::new("Save Slots"),
+                Node {
+                    display: Display::Grid,
+                    row_gap: Px(10.0),
+                    column_gap: Px(30.0),
+                    align_items: AlignItems::Center,
+                    grid_template_columns: vec![GridTrack::px(600.0), GridTrack::px(120.0)],
+                    ..default()
+                },
+                Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+                    for (slot, available) in available.into_iter().enumerate() {
+                        parent.spawn((
+                            widget::label(LocalizedText::new(SAVE_EMPTY), grid_font.clone()),
+                            SlotLabel(slot),
+                        ));
+                        if !available {
+                            parent.spawn(Node::default());
+                        } else if menu == Menu::Save {
+                            parent.spawn(widget::button_size(
+                                LocalizedText::new(action),
+                                grid_font.clone(),
+                                Vec2::new(100.0, 40.0),
+                                move |_: On<Pointer<Click>>, mut commands: Commands| {
+                                    commands.queue(save_game(slot));
+                                },
+                            ));
+                        } else {
+                            parent.spawn(widget::button_size(
+                                LocalizedText::new(action),
+                                grid_font.clone(),
+                                Vec2::new(100.0, 40.0),
+                                move |_: On<Pointer<Click>>, mut commands: Commands| {
+                                    commands.queue(load_game(slot));
+                                },
+                            ));
+                        }
+                    }
+                })),
+            ),
+            widget::button(LocalizedText::new(BACK), font, go_back_on_click),
+        ],
+    ));
+}
+
+fn update_slot_labels(
+    game_list: Option<Res<GameList>>,
+    slots: Res<SaveSlots>,
+    mut label_query: Query<(&SlotLabel, &mut LocalizedText)>,
+) {
+    for (SlotLabel(slot), mut text) in &mut label_query {
+        let n = slot + 1;
+        text.set_if_neq(match slots.0.get(*slot) {
+            Some(Some(meta)) => describe(n, meta, game_list.as_deref()),
+            _ => LocalizedText::new(SAVE_SLOT)
+                .with_arg("n", n)
+                .with_arg("description", TextArg::Key(SAVE_EMPTY.into())),
+        });
+    }
+}
+
+/// 第 `n` 个存档位中存档的游戏名称、保存时间和游戏时间。
+fn describe(n: usize, meta: &SaveMeta, game_list: Option<&GameList>) -> LocalizedText {
+    let name = game_list
+        .and_then(|list| list.games.iter().find(|game| game.plugin == meta.game))
+        .map_or(TextArg::Text(meta.game.clone()), |game| {
+            TextArg::Key(game.name.clone().into())
+        });
+    let seconds = meta.playtime.as_secs();
+    LocalizedText::new(SAVE_SLOT_SAVED)
+        .with_arg("n", n)
+        .with_arg("game", name)
+        .with_arg("time", format_timestamp(meta.timestamp))
+        .with_arg("playtime", format!("{}:{:02}", seconds / 60, seconds % 60))
+}
+
+/// 把 Unix 时间戳格式化为 UTC 的 `YYYY-MM-DD HH:MM`。
+fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let minutes = timestamp % 86_400 / 60;
+    // 从 1970-01-01 起的天数转换为公历日期，见 <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>。
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02}",
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+fn go_back_on_click(
+    _: On<Pointer<Click>>,
+    screen: Res<State<Screen>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    next_menu.set(if screen.get() == &Screen::Title {
+        Menu::Main
+    } else {
+        Menu::Pause
+    });
+}
+
+fn go_back(screen: Res<State<Screen>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(if screen.get() == &Screen::Title {
+        Menu::Main
+    } else {
+        Menu::Pause
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+        assert_eq!(format_timestamp(1_792_318_500), "2026-10-18 10:15");
+    }
+}
@@ -10,7 +10,10 @@ use bevy::{
 use crate::{
     AppSystems,
     games::game_list::{GameList, OpenGameEvent},
-    i18n::config::SELECT_GAME_TITLE,
+    i18n::{
+        LocalizedText,
+        config::{PAUSE_QUIT_TO_TITLE, SELECT_GAME_TITLE},
+    },
     menus::Menu,
     theme::{
         palette::{BUTTON_PRESSED_BACKGROUND, HEADER_TEXT},
@@ -30,34 +33,33 @@ pub(super) fn plugin(app: &mut App) {
 
 fn spawn_select_game_menu(
     mut commands: Commands,
-    lang_res: Res<crate::i18n::LanguageRes>,
     font_res: Res<crate::FntAssets>,
-    game_list: Res<GameList>,
+    game_list: Option<Res<GameList>>,
+    asset_server: Res<AssetServer>,
 ) {
     use bevy::ui::Val::*;
-    let game_items = game_list
-        .games
+    // 游戏目录加载失败时显示空列表。
+    let games = game_list.as_deref().map_or(&[][..], |list| &list.games);
+    let game_items = games
         .iter()
         .enumerate()
         .map(|(index, item)| {
             (
-                lang_res.get(&item.name),
-                lang_res.get(&item.description), // Assuming name is unique
+                LocalizedText::new(item.name.clone()),
+                LocalizedText::new(item.description.clone()),
+                asset_server.load::<Image>(&item.icon),
                 index,
             )
         })
-        .collect::<Vec<_>>()
-        .first()
-        .cloned()
-        .get_or_insert_default()
-        .clone();
+        .collect::<Vec<_>>();
+    let font = font_res.pixel();
 
     commands.spawn((
         widget::ui_root("Select Game Menu"),
         GlobalZIndex(2),
         DespawnOnExit(Menu::SelectGame),
         children![
-            widget::header(lang_res.get(SELECT_GAME_TITLE), font_res.pixel()),
+            widget::header(LocalizedText::new(SELECT_GAME_TITLE), font_res.pixel()),
             (
                 Name::new("Game Button List"),
                 Node {
@@ -69,14 +71,17 @@ fn spawn_select_game_menu(
                     ..default()
                 },
                 Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
-                    for index in 0..20 {
+                    for (name, description, icon, index) in game_items {
                         parent
                             .spawn((
                                 Name::new(format!("Game Button {index}")),
                                 Button,
                                 Node {
                                     min_width: Px(200.0),
+                                    max_width: Px(300.0),
                                     height: Px(200.0),
+                                    padding: UiRect::all(Px(10.0)),
+                                    row_gap: Px(5.0),
                                     align_items: AlignItems::Center,
                                     justify_content: JustifyContent::Center,
                                     flex_direction: FlexDirection::Column,
@@ -87,17 +92,32 @@ fn spawn_select_game_menu(
                                     should_block_lower: false,
                                     ..default()
                                 },
-                                children![(
-                                    Text::new(game_items.0.to_string()),
-                                    TextFont::from_font_size(24.0),
-                                    TextColor::from(BUTTON_PRESSED_BACKGROUND),
-                                    Pickable::IGNORE,
-                                ),],
+                                children![
+                                    (
+                                        ImageNode::new(icon),
+                                        Node {
+                                            width: Px(64.0),
+                                            height: Px(64.0),
+                                            ..default()
+                                        },
+                                        Pickable::IGNORE,
+                                    ),
+                                    (
+                                        name,
+                                        TextFont::from_font_size(24.0).with_font(font.clone()),
+                                        TextColor::from(BUTTON_PRESSED_BACKGROUND),
+                                        Pickable::IGNORE,
+                                    ),
+                                    (
+                                        description,
+                                        TextFont::from_font_size(14.0).with_font(font.clone()),
+                                        TextColor::from(BUTTON_PRESSED_BACKGROUND),
+                                        Pickable::IGNORE,
+                                    ),
+                                ],
                             ))
                             .observe(move |_: On<Pointer<Click>>, mut commands: Commands| {
-                                commands.trigger(OpenGameEvent {
-                                    index: game_items.2 as u8,
-                                });
+                                commands.trigger(OpenGameEvent { index: index as u8 });
                             });
                     }
                 })),
@@ -112,7 +132,7 @@ fn spawn_select_game_menu(
                     ..default()
                 },
                 children![widget::button(
-                    lang_res.get(crate::i18n::config::PAUSE_QUIT_TO_TITLE),
+                    LocalizedText::new(PAUSE_QUIT_TO_TITLE),
                     font_res.pixel(),
                     go_back_on_click,
                 )],
@@ -2,16 +2,23 @@
 //!
 //! 其他设置和辅助功能选项应放在此处。
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+use bevy::{
+    ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
 
 use crate::{
     FntAssets,
+    audio::VolumeBus,
     i18n::{
-        LanguageRes,
-        config::{BACK, SETTINGS_MASTER_VOLUME, SETTINGS_TITLE},
+        LanguageRes, LocalizedText, TextArg,
+        config::{
+            BACK, LANGUAGE_TITLE, SETTINGS_FULLSCREEN, SETTINGS_OFF, SETTINGS_ON, SETTINGS_TITLE,
+            SETTINGS_TOGGLE, SETTINGS_VOLUME_PERCENT,
+        },
     },
     menus::Menu,
     screens::Screen,
+    settings::Settings,
     theme::prelude::*,
 };
 
@@ -22,31 +29,34 @@ pub(super) fn plugin(app: &mut App) {
         go_back.run_if(in_state(Menu::Settings).and(input_just_pressed(KeyCode::Escape))),
     );
 
-    app.register_type::<GlobalVolumeLabel>();
+    app.register_type::<VolumeLabel>();
+    app.register_type::<FullscreenLabel>();
+    app.register_type::<LanguageLabel>();
     app.add_systems(
         Update,
-        update_global_volume_label.run_if(in_state(Menu::Settings)),
+        (
+            update_volume_labels,
+            update_fullscreen_label,
+            update_language_label,
+        )
+            .run_if(in_state(Menu::Settings)),
     );
 }
 
-fn spawn_settings_menu(
-    mut commands: Commands,
-    lang_res: Res<LanguageRes>,
-    font_res: Res<FntAssets>,
-) {
+fn spawn_settings_menu(mut commands: Commands, font_res: Res<FntAssets>) {
     commands.spawn((
         widget::ui_root("Settings Menu"),
         GlobalZIndex(2),
         DespawnOnExit(Menu::Settings),
         children![
-            widget::header(lang_res.get(SETTINGS_TITLE), font_res.pixel()),
-            settings_grid(font_res.pixel(), &lang_res.get(SETTINGS_MASTER_VOLUME)),
-            widget::button(lang_res.get(BACK), font_res.pixel(), go_back_on_click),
+            widget::header(LocalizedText::new(SETTINGS_TITLE), font_res.pixel()),
+            settings_grid(font_res.pixel()),
+            widget::button(LocalizedText::new(BACK), font_res.pixel(), go_back_on_click),
         ],
     ));
 }
 
-fn settings_grid(font: Handle<Font>, label: &str) -> impl Bundle {
+fn settings_grid(font: Handle<Font>) -> impl Bundle {
     (
         Name::new("Settings Grid"),
         Node {
@@ -56,28 +66,45 @@ fn settings_grid(font: Handle<Font>, label: &str) -> impl Bundle {
             grid_template_columns: RepeatedGridTrack::px(2, 400.0),
             ..default()
         },
-        children![
-            (
-                widget::label(label, font.clone()),
-                Node {
-                    justify_self: JustifySelf::End,
-                    ..default()
-                }
-            ),
-            global_volume_widget(font),
-        ],
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            for bus in VolumeBus::ALL {
+                parent.spawn(setting_label(bus.name(), font.clone()));
+                parent.spawn(volume_widget(bus, font.clone()));
+            }
+            parent.spawn(setting_label(SETTINGS_FULLSCREEN, font.clone()));
+            parent.spawn(fullscreen_widget(font.clone()));
+            parent.spawn(setting_label(LANGUAGE_TITLE, font.clone()));
+            parent.spawn(language_widget(font));
+        })),
     )
 }
 
-fn global_volume_widget(font: Handle<Font>) -> impl Bundle {
+fn setting_label(key: &'static str, font: Handle<Font>) -> impl Bundle {
     (
-        Name::new("Global Volume Widget"),
+        widget::label(LocalizedText::new(key), font),
+        Node {
+            justify_self: JustifySelf::End,
+            ..default()
+        },
+    )
+}
+
+fn volume_widget(bus: VolumeBus, font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new(format!("{bus:?} Volume Widget")),
         Node {
             justify_self: JustifySelf::Start,
             ..default()
         },
         children![
-            widget::button_small("-", font.clone(), lower_global_volume),
+            widget::button_small(
+                "-",
+                font.clone(),
+                move |_: On<Pointer<Click>>, mut settings: ResMut<Settings>| {
+                    let volume = settings.volume.get(bus) - VOLUME_STEP;
+                    settings.volume.set(bus, volume.max(MIN_VOLUME));
+                },
+            ),
             (
                 Name::new("Current Volume"),
                 Node {
@@ -85,36 +112,137 @@ fn global_volume_widget(font: Handle<Font>) -> impl Bundle {
                     justify_content: JustifyContent::Center,
                     ..default()
                 },
-                children![(widget::label("", font.clone()), GlobalVolumeLabel)],
+                children![(
+                    widget::label(LocalizedText::new(SETTINGS_VOLUME_PERCENT), font.clone()),
+                    VolumeLabel(bus),
+                )],
+            ),
+            widget::button_small(
+                "+",
+                font,
+                move |_: On<Pointer<Click>>, mut settings: ResMut<Settings>| {
+                    let volume = settings.volume.get(bus) + VOLUME_STEP;
+                    settings.volume.set(bus, volume.min(MAX_VOLUME));
+                },
             ),
-            widget::button_small("+", font, raise_global_volume),
         ],
     )
 }
 
 const MIN_VOLUME: f32 = 0.0;
 const MAX_VOLUME: f32 = 3.0;
+const VOLUME_STEP: f32 = 0.1;
+
+/// 显示一条音量总线当前音量的标签。
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct VolumeLabel(VolumeBus);
+
+fn update_volume_labels(
+    settings: Res<Settings>,
+    mut label_query: Query<(&VolumeLabel, &mut LocalizedText)>,
+) {
+    for (VolumeLabel(bus), mut text) in &mut label_query {
+        let percent = TextArg::Float((100.0 * settings.volume.get(*bus)).into(), 0);
+        text.set_if_neq(LocalizedText::new(SETTINGS_VOLUME_PERCENT).with_arg("percent", percent));
+    }
+}
+
+fn fullscreen_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Fullscreen Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            align_items: AlignItems::Center,
+            column_gap: Px(10.0),
+            ..default()
+        },
+        children![
+            (
+                widget::label(LocalizedText::new(SETTINGS_OFF), font.clone()),
+                FullscreenLabel
+            ),
+            widget::button_size(
+                LocalizedText::new(SETTINGS_TOGGLE),
+                font,
+                Vec2::new(120.0, 40.0),
+                toggle_fullscreen
+            ),
+        ],
+    )
+}
 
-fn lower_global_volume(_: On<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
-    let linear = (global_volume.volume.to_linear() - 0.1).max(MIN_VOLUME);
-    global_volume.volume = Volume::Linear(linear);
+fn toggle_fullscreen(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.fullscreen = !settings.fullscreen;
 }
 
-fn raise_global_volume(_: On<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
-    let linear = (global_volume.volume.to_linear() + 0.1).min(MAX_VOLUME);
-    global_volume.volume = Volume::Linear(linear);
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct FullscreenLabel;
+
+fn update_fullscreen_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut LocalizedText, With<FullscreenLabel>>,
+) {
+    label.set_if_neq(LocalizedText::new(if settings.fullscreen {
+        SETTINGS_ON
+    } else {
+        SETTINGS_OFF
+    }));
 }
 
+fn language_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Language Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            align_items: AlignItems::Center,
+            column_gap: Px(10.0),
+            ..default()
+        },
+        children![
+            (widget::label("", font.clone()), LanguageLabel),
+            widget::button_size(
+                LocalizedText::new(SETTINGS_TOGGLE),
+                font,
+                Vec2::new(120.0, 40.0),
+                next_language
+            ),
+        ],
+    )
+}
+
+/// 切换到语言清单中的下一种语言。
+fn next_language(
+    _: On<Pointer<Click>>,
+    lang_res: Res<LanguageRes>,
+    mut settings: ResMut<Settings>,
+) {
+    let languages = &lang_res.language_list;
+    let Some(index) = languages
+        .iter()
+        .position(|language| language == lang_res.language())
+    else {
+        return;
+    };
+    if let Some(next) = languages.get((index + 1) % languages.len()) {
+        settings.language = next.clone();
+    }
+}
+
+/// 显示当前语言名称的标签。名称总是用语言自身书写，所以不使用 [`LocalizedText`]。
 #[derive(Component, Reflect)]
 #[reflect(Component)]
-struct GlobalVolumeLabel;
+struct LanguageLabel;
 
-fn update_global_volume_label(
-    global_volume: Res<GlobalVolume>,
-    mut label: Single<&mut Text, With<GlobalVolumeLabel>>,
+fn update_language_label(
+    lang_res: Res<LanguageRes>,
+    mut label: Single<&mut Text, With<LanguageLabel>>,
 ) {
-    let percent = 100.0 * global_volume.volume.to_linear();
-    label.0 = format!("{percent:3.0}%");
+    let value = lang_res.language_name(lang_res.language());
+    if label.0 != value {
+        label.0 = value;
+    }
 }
 
 fn go_back_on_click(
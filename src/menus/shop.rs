@@ -0,0 +1,265 @@
+//! 商店菜单：买入种子，卖出收获的作物。
+//!
+//! 每行显示一种商品的图标、名称、买入价、卖出价和背包中的数量。金币不足时买入价显示为红色。
+
+use bevy::{
+    ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{
+    FntAssets,
+    games::{
+        gold::Gold,
+        inventory::{Inventory, ItemData},
+        player::Player,
+        shop::ShopData,
+    },
+    i18n::{
+        LocalizedText,
+        config::{
+            BACK, GOLD, SHOP_BUY, SHOP_BUY_PRICE, SHOP_ITEM, SHOP_OWNED, SHOP_SELL,
+            SHOP_SELL_PRICE, SHOP_TITLE,
+        },
+    },
+    menus::Menu,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ShopLabel>();
+    app.register_type::<ShopAction>();
+
+    app.add_systems(OnEnter(Menu::Shop), spawn_shop_menu);
+    app.add_systems(
+        Update,
+        (
+            update_shop_labels.run_if(resource_exists::<ShopData>),
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+        )
+            .run_if(in_state(Menu::Shop)),
+    );
+}
+
+/// 价格表中各列的宽度：图标、名称、买入价、卖出价、数量、买入按钮、卖出按钮。
+const COLUMNS: [f32; 7] = [48.0, 220.0, 120.0, 120.0, 100.0, 90.0, 90.0];
+
+/// 商店菜单中需要随金币和背包更新的标签。
+#[derive(Component, Reflect, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+enum ShopLabel {
+    Gold,
+    /// [`ShopData::entries`] 中该商品的买入价。
+    BuyPrice(usize),
+    /// 背包中该商品的数量。
+    Owned(usize),
+}
+
+/// 商店菜单中的买入或卖出按钮，对应 [`ShopData::entries`] 中的索引。
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+enum ShopAction {
+    Buy(usize),
+    Sell(usize),
+}
+
+/// 一行商品。
+struct ShopRow {
+    icon: Option<Handle<Image>>,
+    name: LocalizedText,
+    buy: Option<u32>,
+    sell: Option<u32>,
+}
+
+fn spawn_shop_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    font_res: Res<FntAssets>,
+    shop: Option<Res<ShopData>>,
+    items: Option<Res<ItemData>>,
+) {
+    let font = font_res.pixel();
+    // 价格表加载失败时显示空的商店。
+    let entries = shop.as_deref().map_or(&[][..], |shop| &shop.entries);
+    let rows = entries
+        .iter()
+        .map(|entry| {
+            let def = items.as_ref().and_then(|items| items.get(&entry.item));
+            ShopRow {
+                icon: def.map(|def| asset_server.load(&def.icon)),
+                name: LocalizedText::new(def.map_or(&entry.item, |def| &def.name).clone()),
+                buy: entry.buy,
+                sell: entry.sell,
+            }
+        })
+        .collect::<Vec<_>>();
+    let grid_font = font.clone();
+
+    commands.spawn((
+        widget::ui_root("Shop Menu"),
+        GlobalZIndex(2),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        DespawnOnExit(Menu::Shop),
+        children![
+            widget::header(LocalizedText::new(SHOP_TITLE), font.clone()),
+            (
+                widget::label(LocalizedText::new(GOLD), font.clone()),
+                ShopLabel::Gold
+            ),
+            (
+                Name::new("Price Table"),
+                Node {
+                    display: Display::Grid,
+                    row_gap: Px(10.0),
+                    column_gap: Px(10.0),
+                    align_items: AlignItems::Center,
+                    grid_template_columns: COLUMNS.map(GridTrack::px).to_vec(),
+                    ..default()
+                },
+                Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+                    spawn_price_table(parent, rows, grid_font);
+                })),
+            ),
+            widget::button(LocalizedText::new(BACK), font, close_menu),
+        ],
+    ));
+}
+
+fn spawn_price_table(parent: &mut ChildSpawner, rows: Vec<ShopRow>, font: Handle<Font>) {
+    // 表头：图标列和按钮列没有标题。
+    parent.spawn(Node::default());
+    for key in [SHOP_ITEM, SHOP_BUY_PRICE, SHOP_SELL_PRICE, SHOP_OWNED] {
+        parent.spawn(widget::label(LocalizedText::new(key), font.clone()));
+    }
+    parent.spawn(Node::default());
+    parent.spawn(Node::default());
+
+    let price = |price: Option<u32>| price.map_or("-".to_string(), |price| price.to_string());
+    for (index, row) in rows.into_iter().enumerate() {
+        match row.icon {
+            Some(icon) => parent.spawn((
+                ImageNode::new(icon),
+                Node {
+                    width: Px(COLUMNS[0]),
+                    height: Px(COLUMNS[0]),
+                    ..default()
+                },
+            )),
+            None => parent.spawn(Node::default()),
+        };
+        parent.spawn(widget::label(row.name, font.clone()));
+        parent.spawn((
+            widget::label(price(row.buy), font.clone()),
+            ShopLabel::BuyPrice(index),
+        ));
+        parent.spawn(widget::label(price(row.sell), font.clone()));
+        parent.spawn((widget::label("", font.clone()), ShopLabel::Owned(index)));
+        for (key, action, available) in [
+            (SHOP_BUY, ShopAction::Buy(index), row.buy.is_some()),
+            (SHOP_SELL, ShopAction::Sell(index), row.sell.is_some()),
+        ] {
+            if available {
+                parent.spawn((
+                    widget::button_size(
+                        LocalizedText::new(key),
+                        font.clone(),
+                        Vec2::new(80.0, 40.0),
+                        trade,
+                    ),
+                    action,
+                ));
+            } else {
+                parent.spawn(Node::default());
+            }
+        }
+    }
+}
+
+/// 买入或卖出一个物品。按钮的 [`ShopAction`] 位于被点击的内部按钮的父实体上。
+fn trade(
+    click: On<Pointer<Click>>,
+    parent_query: Query<&ChildOf>,
+    action_query: Query<&ShopAction>,
+    shop: Option<Res<ShopData>>,
+    items: Option<Res<ItemData>>,
+    gold: Option<ResMut<Gold>>,
+    mut inventory: Single<&mut Inventory, With<Player>>,
+) {
+    let Some(action) = parent_query
+        .get(click.entity)
+        .ok()
+        .and_then(|child_of| action_query.get(child_of.parent()).ok())
+    else {
+        return;
+    };
+    let (Some(shop), Some(items), Some(mut gold)) = (shop, items, gold) else {
+        return;
+    };
+    let (ShopAction::Buy(index) | ShopAction::Sell(index)) = *action;
+    let Some(entry) = shop.entries.get(index) else {
+        return;
+    };
+    let result = match action {
+        ShopAction::Buy(_) => shop.buy(&items, &entry.item, &mut gold, &mut inventory),
+        ShopAction::Sell(_) => shop.sell(&entry.item, &mut gold, &mut inventory),
+    };
+    if let Err(error) = result {
+        info!("Cannot trade `{}`: {error}", entry.item);
+    }
+}
+
+/// 更新金币、背包中的数量和买入价的颜色。
+fn update_shop_labels(
+    shop: Res<ShopData>,
+    gold: Option<Res<Gold>>,
+    inventory: Single<&Inventory, With<Player>>,
+    mut label_query: Query<(
+        &ShopLabel,
+        &mut Text,
+        &mut TextColor,
+        Option<&mut LocalizedText>,
+    )>,
+) {
+    let gold = gold.map_or(0, |gold| gold.0);
+    for (label, mut text, mut color, localized) in &mut label_query {
+        match *label {
+            ShopLabel::Gold => {
+                if let Some(mut localized) = localized {
+                    localized.set_if_neq(LocalizedText::new(GOLD).with_arg("gold", gold));
+                }
+            }
+            ShopLabel::BuyPrice(index) => {
+                let affordable = shop
+                    .entries
+                    .get(index)
+                    .and_then(|entry| entry.buy)
+                    .is_none_or(|price| price <= gold);
+                let value = if affordable {
+                    ui_palette::LABEL_TEXT
+                } else {
+                    ui_palette::INVALID_TEXT
+                };
+                if color.0 != value {
+                    color.0 = value;
+                }
+            }
+            ShopLabel::Owned(index) => {
+                let value = shop
+                    .entries
+                    .get(index)
+                    .map_or(0, |entry| inventory.count(&entry.item))
+                    .to_string();
+                if text.0 != value {
+                    text.0 = value;
+                }
+            }
+        }
+    }
+}
+
+fn close_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::None);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::None);
+}
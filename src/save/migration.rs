@@ -0,0 +1,85 @@
+//! 存档格式的版本迁移。
+//!
+//! 修改存档格式时（例如重命名了保存的组件或字段），应增加 [`SAVE_VERSION`]，
+//! 并在 [`MIGRATIONS`] 中添加一个把上一个版本的存档升级到新版本的函数。
+//! 迁移函数可以修改元数据，也可以改写场景的 RON 文本。
+
+use bevy::log::info;
+
+use crate::save::{SAVE_VERSION, SaveError, SaveFile};
+
+/// 把存档从 `from` 版本升级到 `from + 1` 版本的函数。
+pub type Migration = fn(&mut SaveFile) -> Result<(), SaveError>;
+
+/// 所有迁移函数，按起始版本排列。
+const MIGRATIONS: &[(u32, Migration)] = &[];
+
+/// 把存档依次升级到 [`SAVE_VERSION`]。
+pub fn migrate(file: &mut SaveFile) -> Result<(), SaveError> {
+    migrate_with(file, MIGRATIONS, SAVE_VERSION)
+}
+
+fn migrate_with(
+    file: &mut SaveFile,
+    migrations: &[(u32, Migration)],
+    target: u32,
+) -> Result<(), SaveError> {
+    if file.version > target {
+        return Err(SaveError::UnsupportedVersion(file.version));
+    }
+    while file.version < target {
+        let from = file.version;
+        let Some((_, migration)) = migrations.iter().find(|(version, _)| *version == from) else {
+            return Err(SaveError::UnsupportedVersion(from));
+        };
+        migration(file)?;
+        file.version = from + 1;
+        info!("Migrated save from version {from} to {}", file.version);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ron::value::RawValue;
+
+    use super::*;
+    use crate::save::SaveMeta;
+
+    fn rename_game(file: &mut SaveFile) -> Result<(), SaveError> {
+        file.meta.game = format!("{}_v2", file.meta.game);
+        Ok(())
+    }
+
+    #[test]
+    fn test_migrate() {
+        let mut file = SaveFile {
+            version: 1,
+            meta: SaveMeta {
+                timestamp: 0,
+                playtime: Duration::ZERO,
+                game: "farming".to_string(),
+            },
+            scene: RawValue::from_boxed_ron("()".into()).unwrap(),
+        };
+        let migrations: &[(u32, Migration)] = &[(1, rename_game)];
+
+        assert!(migrate_with(&mut file, migrations, 2).is_ok());
+        assert_eq!(file.version, 2);
+        assert_eq!(file.meta.game, "farming_v2");
+        // 已经是最新版本时不做任何事。
+        assert!(migrate_with(&mut file, migrations, 2).is_ok());
+        assert_eq!(file.meta.game, "farming_v2");
+        // 缺少迁移函数或来自更新的版本时无法读取。
+        assert!(matches!(
+            migrate_with(&mut file, migrations, 3),
+            Err(SaveError::UnsupportedVersion(2))
+        ));
+        assert!(matches!(
+            migrate_with(&mut file, migrations, 1),
+            Err(SaveError::UnsupportedVersion(2))
+        ));
+    }
+}
@@ -0,0 +1,394 @@
+//! 存档：把游戏状态保存到存档位，或从存档位读取。
+//!
+//! 需要保存的组件和资源通过 [`RegisterSaved`] 注册，只有带 [`Saved`] 标记的实体会被保存。
+//! 只有 [`MiniGame::SAVEABLE`](crate::games::mini_game::MiniGame::SAVEABLE) 的小游戏可以保存。
+//! 这些数据使用 Bevy 的场景格式序列化，连同 [`SaveMeta`] 元数据和存档格式的版本号一起写入存档位。
+//! 读取旧版本的存档时会先依次运行 [`migration`] 中的迁移函数。
+//!
+//! 读取存档会重新开始存档所属的小游戏。游戏生成初始内容后，删除所有带 [`Saved`] 标记的实体，
+//! 再写入存档中的实体和资源，然后发送 [`GameLoaded`] 消息。精灵等可以重建的组件不会被保存，
+//! 游戏应使用观察者在保存的组件被添加时补全它们。
+
+pub mod migration;
+
+use std::time::Duration;
+
+use bevy::{
+    ecs::entity::EntityHashMap,
+    prelude::*,
+    reflect::GetTypeRegistration,
+    scene::{DynamicSceneBuilder, SceneFilter, serde::SceneDeserializer},
+};
+use ron::{ser::PrettyConfig, value::RawValue};
+use serde::{Deserialize, Serialize, de::DeserializeSeed};
+use thiserror::Error;
+
+use crate::{
+    games::mini_game::{ActiveGame, MiniGameRegistry, RunStats, SelectedGame},
+    screens::Screen,
+    storage::{self, StorageDir, StorageError},
+};
+
+/// 当前的存档格式版本。修改存档格式时应增加此值，并添加对应的迁移函数。
+pub const SAVE_VERSION: u32 = 1;
+/// 存档位的数量。
+pub const SLOT_COUNT: usize = 3;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SaveSlots>();
+    app.add_message::<GameLoaded>();
+
+    app.save_component::<Saved>();
+    app.save_component::<Name>();
+    app.save_component::<Transform>();
+    app.save_component::<ChildOf>();
+
+    app.add_systems(
+        Update,
+        apply_pending_load.run_if(in_state(Screen::Gameplay).and(resource_exists::<PendingLoad>)),
+    );
+}
+
+/// 标记需要保存的实体。读取存档时，带有此标记的实体会被存档中的实体替换。
+#[derive(Component, Reflect, Debug, Default, Clone, Copy)]
+#[reflect(Component)]
+pub struct Saved;
+
+pub trait RegisterSaved {
+    /// 保存带 [`Saved`] 标记的实体上的这种组件。
+    fn save_component<T: Component + GetTypeRegistration>(&mut self) -> &mut Self;
+
+    /// 保存这种资源（如果存在）。
+    fn save_resource<T: Resource + GetTypeRegistration>(&mut self) -> &mut Self;
+}
+
+impl RegisterSaved for App {
+    fn save_component<T: Component + GetTypeRegistration>(&mut self) -> &mut Self {
+        self.register_type::<T>();
+        let mut registry = self.world_mut().get_resource_or_init::<SaveRegistry>();
+        registry.components = std::mem::take(&mut registry.components).allow::<T>();
+        self
+    }
+
+    fn save_resource<T: Resource + GetTypeRegistration>(&mut self) -> &mut Self {
+        self.register_type::<T>();
+        let mut registry = self.world_mut().get_resource_or_init::<SaveRegistry>();
+        registry.resources = std::mem::take(&mut registry.resources).allow::<T>();
+        self
+    }
+}
+
+/// 需要保存的组件和资源类型。
+#[derive(Resource)]
+struct SaveRegistry {
+    components: SceneFilter,
+    resources: SceneFilter,
+}
+
+impl Default for SaveRegistry {
+    fn default() -> Self {
+        Self {
+            components: SceneFilter::deny_all(),
+            resources: SceneFilter::deny_all(),
+        }
+    }
+}
+
+/// 存档的元数据，在选择存档位时显示。
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SaveMeta {
+    /// 保存时的 Unix 时间戳（秒）。
+    pub timestamp: u64,
+    /// 这局游戏进行的时间，不包括暂停的时间。
+    pub playtime: Duration,
+    /// 存档所属小游戏的插件标识。
+    pub game: String,
+}
+
+/// 一个存档位的完整内容。
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SaveFile {
+    /// 存档格式的版本，见 [`SAVE_VERSION`]。
+    pub version: u32,
+    pub meta: SaveMeta,
+    /// 保存的实体和资源，使用 Bevy 的场景格式。
+    pub scene: Box<RawValue>,
+}
+
+impl SaveFile {
+    fn parse(data: &str) -> Result<Self, SaveError> {
+        let mut file: Self = ron::from_str(data)?;
+        migration::migrate(&mut file)?;
+        Ok(file)
+    }
+}
+
+/// 保存或读取失败的原因。
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+    #[error("save slot {0} is empty")]
+    EmptySlot(usize),
+    #[error("no game is running")]
+    NoGame,
+    #[error("the game `{0}` cannot be saved")]
+    NotSaveable(String),
+    #[error("the game `{0}` is not registered")]
+    UnknownGame(String),
+    #[error("unsupported save version {0}")]
+    UnsupportedVersion(u32),
+    #[error("invalid save file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("cannot serialize the game: {0}")]
+    Serialize(#[from] ron::Error),
+    #[error("cannot restore the game: {0}")]
+    Scene(String),
+}
+
+/// 各存档位的元数据，`None` 表示存档位为空或无法读取。打开存档菜单时刷新。
+#[derive(Resource, Default, Debug)]
+pub struct SaveSlots(pub Vec<Option<SaveMeta>>);
+
+impl SaveSlots {
+    /// 重新读取所有存档位。
+    pub fn refresh(&mut self) {
+        self.0 = (0..SLOT_COUNT)
+            .map(|slot| match read_slot(slot) {
+                Ok(file) => Some(file.meta),
+                Err(SaveError::EmptySlot(_)) => None,
+                Err(error) => {
+                    warn!("Cannot read save slot {slot}: {error}");
+                    None
+                }
+            })
+            .collect();
+    }
+}
+
+/// 存档位在本地存储中的名称。
+fn slot_name(slot: usize) -> String {
+    format!("saves/slot_{slot}.ron")
+}
+
+fn read_slot(slot: usize) -> Result<SaveFile, SaveError> {
+    let data =
+        storage::read(StorageDir::Data, &slot_name(slot))?.ok_or(SaveError::EmptySlot(slot))?;
+    SaveFile::parse(&data)
+}
+
+/// 已经读取存档，游戏可以根据恢复的状态更新其他数据。
+#[derive(Message, Debug, Clone, Copy)]
+pub struct GameLoaded;
+
+/// 等待写入世界的存档。
+#[derive(Resource)]
+struct PendingLoad(SaveFile);
+
+/// 把当前游戏保存到存档位的命令。
+pub fn save_game(slot: usize) -> impl Command {
+    move |world: &mut World| {
+        match write_save(world, slot) {
+            Ok(()) => info!("Saved game to slot {slot}"),
+            Err(error) => error!("Cannot save game to slot {slot}: {error}"),
+        }
+        world.resource_mut::<SaveSlots>().refresh();
+    }
+}
+
+fn write_save(world: &mut World, slot: usize) -> Result<(), SaveError> {
+    let ActiveGame::Running(game) = *world.resource::<State<ActiveGame>>().get() else {
+        return Err(SaveError::NoGame);
+    };
+    if !world.resource::<MiniGameRegistry>().is_saveable(game) {
+        return Err(SaveError::NotSaveable(game.to_string()));
+    }
+    let file = snapshot(world, game)?;
+    let data = ron::ser::to_string_pretty(&file, PrettyConfig::default())?;
+    storage::write(StorageDir::Data, &slot_name(slot), &data)?;
+    Ok(())
+}
+
+/// 把带 [`Saved`] 标记的实体和保存的资源序列化为 `game` 的存档。
+fn snapshot(world: &mut World, game: &str) -> Result<SaveFile, SaveError> {
+    let entities = world
+        .query_filtered::<Entity, With<Saved>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    let registry = world.resource::<SaveRegistry>();
+    let scene = DynamicSceneBuilder::from_world(world)
+        .with_component_filter(registry.components.clone())
+        .with_resource_filter(registry.resources.clone())
+        .extract_entities(entities.into_iter())
+        .extract_resources()
+        .build();
+    let scene = scene.serialize(&world.resource::<AppTypeRegistry>().read())?;
+
+    Ok(SaveFile {
+        version: SAVE_VERSION,
+        meta: SaveMeta {
+            timestamp: storage::timestamp(),
+            playtime: world.resource::<RunStats>().elapsed,
+            game: game.to_string(),
+        },
+        scene: RawValue::from_boxed_ron(scene.into_boxed_str())?,
+    })
+}
+
+/// 读取存档位并重新开始存档所属的小游戏的命令。
+pub fn load_game(slot: usize) -> impl Command {
+    move |world: &mut World| {
+        if let Err(error) = start_load(world, slot) {
+            error!("Cannot load game from slot {slot}: {error}");
+        }
+    }
+}
+
+fn start_load(world: &mut World, slot: usize) -> Result<(), SaveError> {
+    let file = read_slot(slot)?;
+    let game = world
+        .resource::<MiniGameRegistry>()
+        .get(&file.meta.game)
+        .ok_or_else(|| SaveError::UnknownGame(file.meta.game.clone()))?;
+    info!("Loading game `{game}` from slot {slot}");
+    world.resource_mut::<SelectedGame>().0 = Some(game);
+    world.insert_resource(PendingLoad(file));
+    world
+        .resource_mut::<NextState<Screen>>()
+        .set(Screen::Loading);
+    Ok(())
+}
+
+/// 等游戏生成初始内容后，用存档替换带 [`Saved`] 标记的实体和保存的资源。
+fn apply_pending_load(world: &mut World) {
+    if *world.resource::<State<ActiveGame>>().get() == ActiveGame::None {
+        return;
+    }
+    let Some(PendingLoad(file)) = world.remove_resource::<PendingLoad>() else {
+        return;
+    };
+    match write_scene(world, &file) {
+        Ok(()) => {
+            world.resource_mut::<RunStats>().elapsed = file.meta.playtime;
+            world.write_message(GameLoaded);
+        }
+        Err(error) => error!("Cannot restore saved game: {error}"),
+    }
+}
+
+fn write_scene(world: &mut World, file: &SaveFile) -> Result<(), SaveError> {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let scene = {
+        let mut deserializer = ron::Deserializer::from_str(file.scene.get_ron())?;
+        SceneDeserializer {
+            type_registry: &type_registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .map_err(|error| deserializer.span_error(error))?
+    };
+
+    let saved = world
+        .query_filtered::<Entity, With<Saved>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for entity in saved {
+        // 子实体可能已经随父实体一起被删除。
+        if let Ok(entity) = world.get_entity_mut(entity) {
+            entity.despawn();
+        }
+    }
+
+    let mut entity_map = EntityHashMap::default();
+    scene
+        .write_to_world_with(world, &mut entity_map, &type_registry)
+        .map_err(|error| SaveError::Scene(error.to_string()))?;
+    for &entity in entity_map.values() {
+        let mut entity = world.entity_mut(entity);
+        // 写入场景时不会运行关系的钩子，重新插入 `ChildOf` 以更新父实体的 `Children`。
+        if let Some(child_of) = entity.take::<ChildOf>() {
+            entity.insert(child_of);
+        }
+        // 读取的实体不属于任何游戏的状态，在离开游戏时清理。
+        entity.insert(DespawnOnExit(Screen::Gameplay));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<RunStats>();
+        app.save_component::<Saved>();
+        app.save_component::<Name>();
+        app.save_component::<Transform>();
+        app.save_component::<ChildOf>();
+        app.save_resource::<Score>();
+        app
+    }
+
+    #[test]
+    fn test_save_round_trip() {
+        let mut app = app();
+        let world = app.world_mut();
+        world.insert_resource(Score(7));
+        world.resource_mut::<RunStats>().elapsed = Duration::from_secs(90);
+        let parent = world
+            .spawn((
+                Saved,
+                Name::new("Parent"),
+                Transform::from_xyz(1.0, 2.0, 3.0),
+            ))
+            .id();
+        world.spawn((Saved, Name::new("Child"), ChildOf(parent)));
+        // 没有标记的实体和没有注册的组件不会被保存。
+        world.spawn((Name::new("Unsaved"), Transform::default()));
+        world.entity_mut(parent).insert(Visibility::Hidden);
+
+        let file = snapshot(world, "farming").unwrap();
+        let data = ron::ser::to_string_pretty(&file, PrettyConfig::default()).unwrap();
+        let file = SaveFile::parse(&data).unwrap();
+        assert_eq!(file.version, SAVE_VERSION);
+        assert_eq!(file.meta.game, "farming");
+        assert_eq!(file.meta.playtime, Duration::from_secs(90));
+
+        // 存档之后游戏状态发生了变化。
+        world.insert_resource(Score(0));
+        world.entity_mut(parent).despawn();
+        world.spawn((Saved, Name::new("Stale")));
+
+        write_scene(world, &file).unwrap();
+        assert_eq!(*world.resource::<Score>(), Score(7));
+        let mut saved = world.query_filtered::<(Entity, &Name), With<Saved>>();
+        let mut names = saved
+            .iter(world)
+            .map(|(_, name)| name.as_str())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["Child", "Parent"]);
+
+        let (parent, _) = saved
+            .iter(world)
+            .find(|(_, name)| name.as_str() == "Parent")
+            .unwrap();
+        let parent = world.entity(parent);
+        assert_eq!(
+            parent.get::<Transform>(),
+            Some(&Transform::from_xyz(1.0, 2.0, 3.0))
+        );
+        assert!(!parent.contains::<Visibility>());
+        assert_eq!(
+            parent.get::<Children>().map(|children| children.len()),
+            Some(1)
+        );
+        // 没有标记的实体保持不变。
+        let mut unsaved = world.query_filtered::<&Name, Without<Saved>>();
+        assert_eq!(unsaved.iter(world).count(), 1);
+    }
+}
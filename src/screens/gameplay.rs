@@ -2,11 +2,9 @@
 
 use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
 
-use crate::{Pause, games::level::spawn_level, menus::Menu, screens::Screen};
+use crate::{Pause, menus::Menu, screens::Screen};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Gameplay), spawn_level);
-
     // 按键切换暂停。
     app.add_systems(
         Update,
@@ -19,6 +17,7 @@ pub(super) fn plugin(app: &mut App) {
             close_menu.run_if(
                 in_state(Screen::Gameplay)
                     .and(not(in_state(Menu::None)))
+                    .and(not(in_state(Menu::Results)))
                     .and(input_just_pressed(KeyCode::KeyP)),
             ),
         ),
@@ -3,7 +3,7 @@
 mod gameplay;
 mod loading;
 mod splash;
-mod title;
+pub mod title;
 
 use bevy::prelude::*;
 
@@ -26,6 +26,5 @@ pub enum Screen {
     Splash,
     Title,
     Loading,
-    RefreshLanguage,
     Gameplay,
 }
@@ -5,12 +5,23 @@ use bevy::prelude::*;
 use crate::{menus::Menu, screens::Screen};
 
 pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<TitleMenu>();
     app.add_systems(OnEnter(Screen::Title), open_main_menu);
     app.add_systems(OnExit(Screen::Title), close_menu);
 }
 
-fn open_main_menu(mut next_menu: ResMut<NextState<Menu>>) {
-    next_menu.set(Menu::Main);
+/// 下一次进入标题屏幕时打开的菜单，打开后恢复为 [`Menu::Main`]。
+#[derive(Resource)]
+pub struct TitleMenu(pub Menu);
+
+impl Default for TitleMenu {
+    fn default() -> Self {
+        Self(Menu::Main)
+    }
+}
+
+fn open_main_menu(mut title_menu: ResMut<TitleMenu>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(std::mem::take(&mut *title_menu).0);
 }
 
 fn close_menu(mut next_menu: ResMut<NextState<Menu>>) {
@@ -0,0 +1,207 @@
+//! 玩家设置：音量、语言和窗口模式等，在多次启动之间保留。
+//!
+//! [`Settings`] 在启动时从本地存储读取，每次修改后写回。菜单只修改 [`Settings`]，
+//! 再由这里把设置应用到 [`VolumeBuses`]、[`LanguageRes`] 和主窗口。
+//! 添加新的选项时，在 [`Settings`] 和 [`SettingsFile`] 中添加字段并在 [`apply_settings`] 中应用，
+//! 旧的设置文件中缺少的字段会使用默认值。改名或改变格式的字段在 [`SettingsFile`] 中迁移。
+
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowMode},
+};
+use ron::value::RawValue;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{
+    audio::VolumeBuses,
+    i18n::{LanguageId, LanguageRes},
+    storage::{self, StorageDir},
+};
+
+/// 设置在本地存储中的名称。
+const SETTINGS_NAME: &str = "settings.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Settings>();
+    app.insert_resource(Settings::load());
+
+    app.add_systems(Startup, apply_settings);
+    app.add_systems(
+        Update,
+        (apply_settings, save_settings)
+            .run_if(resource_changed::<Settings>.and(not(resource_added::<Settings>))),
+    );
+}
+
+/// 玩家设置。
+#[derive(Resource, Reflect, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[reflect(Resource)]
+#[serde(from = "SettingsFile")]
+pub struct Settings {
+    /// 各音量总线的音量。
+    pub volume: VolumeBuses,
+    /// 界面语言。
+    pub language: LanguageId,
+    /// 是否以无边框全屏显示。
+    pub fullscreen: bool,
+}
+
+/// 设置文件的内容，包括旧版本中使用的字段。
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct SettingsFile {
+    #[serde(deserialize_with = "present")]
+    volume: Option<VolumeBuses>,
+    /// 旧版本中只有主音量，现在是 `volume.master`。
+    #[serde(deserialize_with = "present")]
+    master_volume: Option<f32>,
+    /// 语言的原始文本，见 [`parse_language`]。
+    #[serde(deserialize_with = "present")]
+    language: Option<Box<RawValue>>,
+    fullscreen: bool,
+}
+
+/// 解析设置文件中的语言。旧版本把语言保存为枚举值（例如 `EnUs`），现在是 BCP-47 标签（例如 `"en-US"`）。
+/// RON 的反序列化无法同时接受标识符和字符串，所以直接检查原始文本。
+fn parse_language(raw: &RawValue) -> Option<LanguageId> {
+    match raw.get_ron().trim() {
+        "ZhCn" => LanguageId::parse("zh-CN"),
+        "EnUs" => LanguageId::parse("en-US"),
+        _ => LanguageId::parse(&raw.into_rust::<String>().ok()?),
+    }
+}
+
+/// 区分文件中缺少的字段（`None`）和存在的字段，字段的值不需要写成 `Some(..)`。
+fn present<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error> {
+    T::deserialize(deserializer).map(Some)
+}
+
+impl From<SettingsFile> for Settings {
+    fn from(file: SettingsFile) -> Self {
+        let volume = match (file.volume, file.master_volume) {
+            (Some(volume), _) => volume,
+            (None, Some(master)) => VolumeBuses {
+                master,
+                ..default()
+            },
+            (None, None) => default(),
+        };
+        Self {
+            volume,
+            language: file
+                .language
+                .map(|raw| {
+                    parse_language(&raw).unwrap_or_else(|| {
+                        warn!("Invalid language `{}` in settings", raw.get_ron());
+                        default()
+                    })
+                })
+                .unwrap_or_default(),
+            fullscreen: file.fullscreen,
+        }
+    }
+}
+
+impl Settings {
+    /// 读取保存的设置，不存在或无法读取时使用默认设置。
+    fn load() -> Self {
+        match storage::read(StorageDir::Config, SETTINGS_NAME) {
+            Ok(Some(data)) => ron::from_str(&data).unwrap_or_else(|error| {
+                warn!("Invalid settings file, using defaults: {error}");
+                default()
+            }),
+            Ok(None) => default(),
+            Err(error) => {
+                warn!("Cannot read settings, using defaults: {error}");
+                default()
+            }
+        }
+    }
+}
+
+fn apply_settings(
+    settings: Res<Settings>,
+    mut buses: ResMut<VolumeBuses>,
+    mut lang_res: ResMut<LanguageRes>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    if *buses != settings.volume {
+        *buses = settings.volume;
+    }
+    if *lang_res.language() != settings.language {
+        lang_res.set_language(settings.language.clone());
+    }
+    let mode = if settings.fullscreen {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    };
+    if window.mode != mode {
+        window.mode = mode;
+    }
+}
+
+fn save_settings(settings: Res<Settings>) {
+    let result = ron::ser::to_string_pretty(&*settings, default())
+        .map_err(|error| error.to_string())
+        .and_then(|data| {
+            storage::write(StorageDir::Config, SETTINGS_NAME, &data)
+                .map_err(|error| error.to_string())
+        });
+    if let Err(error) = result {
+        error!("Cannot save settings: {error}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_settings() {
+        let settings = Settings {
+            volume: VolumeBuses {
+                music: 0.5,
+                ..default()
+            },
+            language: LanguageId::parse("zh-CN").unwrap(),
+            fullscreen: true,
+        };
+        let data = ron::ser::to_string_pretty(&settings, default()).unwrap();
+        assert_eq!(ron::from_str::<Settings>(&data).unwrap(), settings);
+        // 缺少的字段使用默认值。
+        assert_eq!(
+            ron::from_str::<Settings>("()").unwrap(),
+            Settings::default()
+        );
+    }
+
+    #[test]
+    fn test_migrate_master_volume() {
+        let settings: Settings = ron::from_str("(master_volume: 0.25, fullscreen: true)").unwrap();
+        assert_eq!(settings.volume.master, 0.25);
+        assert_eq!(settings.volume.music, 1.0);
+        assert!(settings.fullscreen);
+        // 同时存在时使用新的字段。
+        let settings: Settings =
+            ron::from_str("(volume: (master: 0.5), master_volume: 0.25)").unwrap();
+        assert_eq!(settings.volume.master, 0.5);
+    }
+
+    #[test]
+    fn test_migrate_language() {
+        let settings: Settings = ron::from_str("(language: ZhCn)").unwrap();
+        assert_eq!(settings.language, LanguageId::parse("zh-CN").unwrap());
+        let settings: Settings = ron::from_str("(language: EnUs, fullscreen: true)").unwrap();
+        assert_eq!(settings.language, LanguageId::parse("en-US").unwrap());
+        assert!(settings.fullscreen);
+        let settings: Settings = ron::from_str(r#"(language: "zh-CN")"#).unwrap();
+        assert_eq!(settings.language, LanguageId::parse("zh-CN").unwrap());
+        // 无效的语言只重置语言，保留其他设置。
+        let settings: Settings = ron::from_str("(language: Klingon, fullscreen: true)").unwrap();
+        assert_eq!(settings.language, LanguageId::default());
+        assert!(settings.fullscreen);
+    }
+}
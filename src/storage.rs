@@ -0,0 +1,182 @@
+//! 本地存储：存档和设置等需要跨越多次启动保留的数据。
+//!
+//! 原生平台上每一项是平台配置目录或数据目录下的一个文件，Web 平台上则是 `localStorage` 中的一项。
+//! 旧版本在 `localStorage` 中使用的存档位键会在第一次读取时迁移到新的键（见 `legacy_key`）。
+
+use thiserror::Error;
+
+/// 应用在配置目录、数据目录和 `localStorage` 中使用的名称。
+const APP_NAME: &str = "wtf_farming";
+
+/// 存放数据的位置。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageDir {
+    /// 设置等配置。
+    Config,
+    /// 存档等游戏数据。
+    Data,
+}
+
+/// 读取或写入失败的原因。
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("there is no place to store data on this platform")]
+    Unavailable,
+    #[cfg(not(target_family = "wasm"))]
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[cfg(target_family = "wasm")]
+    #[error("storage error: {0}")]
+    Web(String),
+}
+
+/// 旧版本中存档位 `saves/slot_N.ron` 在 `localStorage` 中的键 `wtf_farming.save.N`。
+/// 其他数据项没有旧的键。
+#[cfg(any(target_family = "wasm", test))]
+fn legacy_key(dir: StorageDir, name: &str) -> Option<String> {
+    let slot = name.strip_prefix("saves/slot_")?.strip_suffix(".ron")?;
+    (dir == StorageDir::Data && slot.parse::<usize>().is_ok())
+        .then(|| format!("{APP_NAME}.save.{slot}"))
+}
+
+#[cfg(not(target_family = "wasm"))]
+mod platform {
+    use std::{env, fs, io, path::PathBuf, time::SystemTime};
+
+    use super::{APP_NAME, StorageDir, StorageError};
+
+    /// 平台的配置目录或数据目录：Windows 上均为 `%APPDATA%`，macOS 上均为
+    /// `~/Library/Application Support`，其他平台遵循 XDG 规范，
+    /// 分别为 `$XDG_CONFIG_HOME` 或 `~/.config`，`$XDG_DATA_HOME` 或 `~/.local/share`。
+    fn base_dir(dir: StorageDir) -> Option<PathBuf> {
+        let home = || env::var_os("HOME").map(PathBuf::from);
+        if cfg!(target_os = "windows") {
+            env::var_os("APPDATA").map(PathBuf::from)
+        } else if cfg!(target_os = "macos") {
+            home().map(|home| home.join("Library/Application Support"))
+        } else {
+            let (var, default) = match dir {
+                StorageDir::Config => ("XDG_CONFIG_HOME", ".config"),
+                StorageDir::Data => ("XDG_DATA_HOME", ".local/share"),
+            };
+            env::var_os(var)
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from)
+                .or_else(|| home().map(|home| home.join(default)))
+        }
+    }
+
+    fn path(dir: StorageDir, name: &str) -> Result<PathBuf, StorageError> {
+        let base = base_dir(dir).ok_or(StorageError::Unavailable)?;
+        Ok(base.join(APP_NAME).join(name))
+    }
+
+    pub fn read(dir: StorageDir, name: &str) -> Result<Option<String>, StorageError> {
+        match fs::read_to_string(path(dir, name)?) {
+            Ok(data) => Ok(Some(data)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn write(dir: StorageDir, name: &str, data: &str) -> Result<(), StorageError> {
+        let path = path(dir, name)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // 先写入临时文件再重命名，避免写到一半时损坏原有的内容。
+        let mut temp = path.clone().into_os_string();
+        temp.push(".tmp");
+        fs::write(&temp, data)?;
+        fs::rename(&temp, &path)?;
+        Ok(())
+    }
+
+    pub fn timestamp() -> u64 {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs())
+    }
+}
+
+#[cfg(target_family = "wasm")]
+mod platform {
+    use super::{APP_NAME, StorageDir, StorageError, legacy_key};
+
+    fn local_storage() -> Result<web_sys::Storage, StorageError> {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .ok_or(StorageError::Unavailable)
+    }
+
+    fn key(dir: StorageDir, name: &str) -> String {
+        let dir = match dir {
+            StorageDir::Config => "config",
+            StorageDir::Data => "data",
+        };
+        format!("{APP_NAME}.{dir}.{name}")
+    }
+
+    fn web_error(error: impl std::fmt::Debug) -> StorageError {
+        StorageError::Web(format!("{error:?}"))
+    }
+
+    pub fn read(dir: StorageDir, name: &str) -> Result<Option<String>, StorageError> {
+        let storage = local_storage()?;
+        let key = key(dir, name);
+        if let Some(data) = storage.get_item(&key).map_err(web_error)? {
+            return Ok(Some(data));
+        }
+        // 把旧版本的键迁移到新的键，之后的读取和写入都使用新的键。
+        let Some(legacy) = legacy_key(dir, name) else {
+            return Ok(None);
+        };
+        let Some(data) = storage.get_item(&legacy).map_err(web_error)? else {
+            return Ok(None);
+        };
+        storage.set_item(&key, &data).map_err(web_error)?;
+        storage.remove_item(&legacy).map_err(web_error)?;
+        Ok(Some(data))
+    }
+
+    pub fn write(dir: StorageDir, name: &str, data: &str) -> Result<(), StorageError> {
+        local_storage()?
+            .set_item(&key(dir, name), data)
+            .map_err(web_error)
+    }
+
+    pub fn timestamp() -> u64 {
+        (js_sys::Date::now() / 1000.0) as u64
+    }
+}
+
+/// 读取一项数据，不存在时返回 `None`。`name` 可以包含 `/` 分隔的子目录。
+pub fn read(dir: StorageDir, name: &str) -> Result<Option<String>, StorageError> {
+    platform::read(dir, name)
+}
+
+/// 覆盖一项数据。
+pub fn write(dir: StorageDir, name: &str, data: &str) -> Result<(), StorageError> {
+    platform::write(dir, name, data)
+}
+
+/// 当前的 Unix 时间戳（秒）。
+pub fn timestamp() -> u64 {
+    platform::timestamp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_key() {
+        assert_eq!(
+            legacy_key(StorageDir::Data, "saves/slot_2.ron").as_deref(),
+            Some("wtf_farming.save.2")
+        );
+        assert_eq!(legacy_key(StorageDir::Config, "saves/slot_2.ron"), None);
+        assert_eq!(legacy_key(StorageDir::Data, "saves/slot_x.ron"), None);
+        assert_eq!(legacy_key(StorageDir::Config, "settings.ron"), None);
+    }
+}
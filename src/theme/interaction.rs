@@ -1,13 +1,11 @@
 use bevy::prelude::*;
 
-use crate::{asset_tracking::LoadResource, audio::sound_effect};
+use crate::audio::{PlaySound, cues};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<InteractionPalette>();
     app.add_systems(Update, apply_interaction_palette);
 
-    app.register_type::<InteractionAssets>();
-    app.load_resource::<InteractionAssets>();
     app.add_observer(play_on_hover_sound_effect);
     app.add_observer(play_on_click_sound_effect);
 }
@@ -38,51 +36,22 @@ fn apply_interaction_palette(
     }
 }
 
-#[derive(Resource, Asset, Clone, Reflect)]
-#[reflect(Resource)]
-struct InteractionAssets {
-    #[dependency]
-    hover: Handle<AudioSource>,
-    #[dependency]
-    click: Handle<AudioSource>,
-}
-
-impl FromWorld for InteractionAssets {
-    fn from_world(world: &mut World) -> Self {
-        let assets = world.resource::<AssetServer>();
-        Self {
-            hover: assets.load("audio/sound_effects/button_hover.ogg"),
-            click: assets.load("audio/sound_effects/button_click.ogg"),
-        }
-    }
-}
-
 fn play_on_hover_sound_effect(
     trigger: On<Pointer<Over>>,
-    mut commands: Commands,
-    interaction_assets: Option<Res<InteractionAssets>>,
+    mut sound_writer: MessageWriter<PlaySound>,
     interaction_query: Query<(), With<Interaction>>,
 ) {
-    let Some(interaction_assets) = interaction_assets else {
-        return;
-    };
-
     if interaction_query.contains(trigger.entity) {
-        commands.spawn(sound_effect(interaction_assets.hover.clone()));
+        sound_writer.write(PlaySound(cues::BUTTON_HOVER));
     }
 }
 
 fn play_on_click_sound_effect(
     trigger: On<Pointer<Click>>,
-    mut commands: Commands,
-    interaction_assets: Option<Res<InteractionAssets>>,
+    mut sound_writer: MessageWriter<PlaySound>,
     interaction_query: Query<(), With<Interaction>>,
 ) {
-    let Some(interaction_assets) = interaction_assets else {
-        return;
-    };
-
     if interaction_query.contains(trigger.entity) {
-        commands.spawn(sound_effect(interaction_assets.click.clone()));
+        sound_writer.write(PlaySound(cues::BUTTON_CLICK));
     }
 }
@@ -6,6 +6,9 @@ pub const LABEL_TEXT: Color = Color::srgb(0.867, 0.827, 0.412);
 /// #fcfbcc 浅米色
 pub const HEADER_TEXT: Color = Color::srgb(0.988, 0.984, 0.800);
 
+/// #e65c5c 浅红色，用于无法执行的操作，例如金币不足
+pub const INVALID_TEXT: Color = Color::srgb(0.902, 0.361, 0.361);
+
 /// #ececec 浅灰色
 pub const BUTTON_TEXT: Color = Color::srgb(0.925, 0.925, 0.925);
 /// #4666bf 深蓝色
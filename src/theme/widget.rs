@@ -8,7 +8,27 @@ use bevy::{
     ui::Val::*,
 };
 
-use crate::theme::{interaction::InteractionPalette, palette::*};
+use crate::{
+    i18n::LocalizedText,
+    theme::{interaction::InteractionPalette, palette::*},
+};
+
+/// 小部件显示的文本：固定的字符串，或随当前语言更新的 [`LocalizedText`]。
+pub trait WidgetText {
+    fn into_text(self) -> impl Bundle;
+}
+
+impl<T: Into<String>> WidgetText for T {
+    fn into_text(self) -> impl Bundle {
+        Text(self.into())
+    }
+}
+
+impl WidgetText for LocalizedText {
+    fn into_text(self) -> impl Bundle {
+        self
+    }
+}
 
 /// 一个填充窗口并将其内容居中的根 UI 节点。
 pub fn ui_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {
@@ -29,38 +49,80 @@ pub fn ui_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {
     )
 }
 
+/// 一个填充窗口的 HUD 根节点，子节点从上到下排列，第一个和最后一个子节点分别贴近窗口的顶部和底部。
+pub fn hud_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {
+    (
+        Name::new(name),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            height: Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::SpaceBetween,
+            padding: UiRect::all(Px(20.0)),
+            ..default()
+        },
+        // 不阻止游戏世界的拾取事件。
+        Pickable::IGNORE,
+    )
+}
+
+/// 一行水平排列的 HUD 元素。
+pub fn hud_bar(name: impl Into<Cow<'static, str>>) -> impl Bundle {
+    (
+        Name::new(name),
+        Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Px(30.0),
+            ..default()
+        },
+        Pickable::IGNORE,
+    )
+}
+
+/// HUD 中的一个文本标签。`marker` 通常是游戏自己的标签枚举，用于找到要更新的 [`LocalizedText`]。
+pub fn hud_label(marker: impl Component, text: impl WidgetText, font: Handle<Font>) -> impl Bundle {
+    (
+        label(text, font),
+        marker,
+        // 不阻止游戏世界的拾取事件。
+        Pickable::IGNORE,
+    )
+}
+
 /// size 80 的标签。比 [`label`] 更大。
-pub fn label_size_80(text: impl Into<String>, font: Handle<Font>) -> impl Bundle {
+pub fn label_size_80(text: impl WidgetText, font: Handle<Font>) -> impl Bundle {
     (
         Name::new("Header"),
-        Text(text.into()),
+        text.into_text(),
         TextFont::from_font_size(80.0).with_font(font),
         TextColor(HEADER_TEXT),
     )
 }
 
 /// 一个简单的标题标签。比 [`label`] 更大。
-pub fn header(text: impl Into<String>, font: Handle<Font>) -> impl Bundle {
+pub fn header(text: impl WidgetText, font: Handle<Font>) -> impl Bundle {
     (
         Name::new("Header"),
-        Text(text.into()),
+        text.into_text(),
         TextFont::from_font_size(40.0).with_font(font),
         TextColor(HEADER_TEXT),
     )
 }
 
 /// 一个简单的文本标签。
-pub fn label(text: impl Into<String>, font: Handle<Font>) -> impl Bundle {
+pub fn label(text: impl WidgetText, font: Handle<Font>) -> impl Bundle {
     (
         Name::new("Label"),
-        Text(text.into()),
+        text.into_text(),
         TextFont::from_font_size(24.0).with_font(font),
         TextColor(LABEL_TEXT),
     )
 }
 
 /// 一个带有文本和由 [`Observer`] 定义的操作的大型圆角按钮。
-pub fn button<E, B, M, I>(text: impl Into<String>, font: Handle<Font>, action: I) -> impl Bundle
+pub fn button<E, B, M, I>(text: impl WidgetText, font: Handle<Font>, action: I) -> impl Bundle
 where
     E: Event + EntityEvent,
     B: Bundle,
@@ -85,7 +147,7 @@ where
 
 /// 一个带有文本和由 [`Observer`] 定义的操作的大型圆角按钮。
 pub fn button_size<E, B, M, I>(
-    text: impl Into<String>,
+    text: impl WidgetText,
     font: Handle<Font>,
     size: Vec2,
     action: I,
@@ -114,11 +176,7 @@ where
 }
 
 /// 一个带有文本和由 [`Observer`] 定义的操作的小型方形按钮。
-pub fn button_small<E, B, M, I>(
-    text: impl Into<String>,
-    font: Handle<Font>,
-    action: I,
-) -> impl Bundle
+pub fn button_small<E, B, M, I>(text: impl WidgetText, font: Handle<Font>, action: I) -> impl Bundle
 where
     E: Event + EntityEvent,
     B: Bundle,
@@ -140,7 +198,7 @@ where
 
 /// 一个带有文本和由 [`Observer`] 定义的操作的简单按钮。按钮的布局由 `button_bundle` 提供。
 fn button_base<E, B, M, I>(
-    text: impl Into<String>,
+    text: impl WidgetText,
     font: Handle<Font>,
     action: I,
     button_bundle: impl Bundle,
@@ -150,7 +208,7 @@ where
     B: Bundle,
     I: IntoObserverSystem<E, B, M>,
 {
-    let text = text.into();
+    let text = text.into_text();
     let action = IntoObserverSystem::into_system(action);
     (
         Name::new("Button"),
@@ -168,7 +226,7 @@ where
                     },
                     children![(
                         Name::new("Button Text"),
-                        Text(text),
+                        text,
                         TextFont {
                             font,
                             font_size: 40.0,